@@ -0,0 +1,157 @@
+use std::{
+    cell::Cell,
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use rsdex_lib::{
+    FieldSet,
+    color::ColorChoice,
+    pokedex::{Pokedex, PokedexSearchResult},
+    search::KeyWord,
+    writing::{BatchWriter, Compression, WriteType},
+};
+
+use crate::output::{self, OutputFormat, View};
+
+///where the results of a batch go
+pub enum Target {
+    ///one csv or jsonl with a `query` column, stdout if there is no file
+    Columns(BatchWriter, Option<(PathBuf, Option<Compression>)>),
+    ///every query under its own heading
+    Sections(View),
+}
+impl Target {
+    ///files have to be csv or jsonl, on stdout the other formats get a section for every query
+    pub fn new(
+        file_path: Option<&Path>,
+        view: View,
+        fields: FieldSet,
+        pretty: bool,
+    ) -> Result<Self, String> {
+        let write_type = match (&view, file_path) {
+            (View::Format(OutputFormat::Write(write_type)), _) => Some(write_type.clone()),
+            (View::Format(OutputFormat::Template(_)), _) => None,
+            (_, Some(path)) if path.as_os_str() != "-" => output::split_path(path).1,
+            //`-p -` writes the default write type like it does without a batch
+            (_, Some(_)) => Some(WriteType::default()),
+            (_, None) => None,
+        };
+        let file = file_path
+            .filter(|path| path.as_os_str() != "-")
+            .map(|path| (path.to_path_buf(), output::split_path(path).0));
+        match write_type
+            .clone()
+            .and_then(|write_type| BatchWriter::new(write_type, fields, pretty))
+        {
+            Some(writer) => Ok(Target::Columns(writer, file)),
+            None if file.is_some() => {
+                Err("a batch can only be written to a csv or jsonl file".into())
+            }
+            None => Ok(Target::Sections(write_type.map_or(view, |write_type| {
+                View::Format(OutputFormat::Write(write_type))
+            }))),
+        }
+    }
+}
+
+///runs every line of `queries` as its own search, `-` reads them from stdin
+///
+///empty lines and lines starting with `#` are skipped, lines that don't parse are reported and left out
+///
+///returns how many pokemon were found over all lines
+pub fn run<P: Pokedex>(
+    pokedex: &P,
+    queries: &Path,
+    target: Target,
+    fields: &FieldSet,
+    color: ColorChoice,
+) -> io::Result<usize> {
+    let mut text = String::new();
+    if queries.as_os_str() == "-" {
+        io::stdin().read_to_string(&mut text)?;
+    } else {
+        text = std::fs::read_to_string(queries)?;
+    }
+    let found = Cell::new(0);
+    let results = text
+        .lines()
+        .enumerate()
+        .map(|(number, line)| (number + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|(number, line)| {
+            match KeyWord::parse(&mut line.split_whitespace().map(String::from)) {
+                Ok(keyword) => {
+                    let mut result = pokedex.search_many(keyword);
+                    result.sort();
+                    found.set(found.get() + result.len());
+                    Some((line, result))
+                }
+                Err(e) => {
+                    eprintln!("line {number} `{line}`: {e}");
+                    None
+                }
+            }
+        });
+
+    let written = match target {
+        Target::Sections(view) => {
+            let mut previous = String::new();
+            for (query, result) in results {
+                //the section stays empty like a search without results prints nothing
+                if result.is_empty() {
+                    eprintln!("`{query}`: sorry we couldn't find any thing in our data");
+                }
+                let mut section = format!(
+                    "# {query}\n{}",
+                    output::render(&result, &view, fields, color)
+                );
+                //json and such don't end with a new line
+                if !section.ends_with('\n') {
+                    section.push('\n');
+                }
+                //a blank line between sections unless the last one ended with one
+                if !previous.is_empty() && !previous.ends_with("\n\n") {
+                    section.insert(0, '\n');
+                }
+                output::print(&section)?;
+                previous = section;
+            }
+            Ok(())
+        }
+        Target::Columns(mut writer, Some((path, compression))) => {
+            let mut file = BufWriter::new(std::fs::File::create(&path)?);
+            match compression {
+                Some(compression) => {
+                    let mut compressed = compression.writer(&mut file)?;
+                    write_columns(&mut compressed, &mut writer, results)?;
+                    compressed.finish()?;
+                }
+                None => write_columns(&mut file, &mut writer, results)?,
+            }
+            file.flush()?;
+            output::print("writing successful\n")?;
+            Ok(())
+        }
+        Target::Columns(mut writer, None) => {
+            let mut stdout = io::stdout().lock();
+            match write_columns(&mut stdout, &mut writer, results).and_then(|_| stdout.flush()) {
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                written => written,
+            }
+        }
+    };
+    written.map(|_| found.get())
+}
+
+fn write_columns<'a>(
+    out: &mut dyn Write,
+    writer: &mut BatchWriter,
+    results: impl Iterator<Item = (&'a str, PokedexSearchResult)>,
+) -> io::Result<()> {
+    writer.begin(out)?;
+    for (query, result) in results {
+        writer.write(out, query, &result)?;
+    }
+    Ok(())
+}
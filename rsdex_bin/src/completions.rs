@@ -0,0 +1,177 @@
+use std::{
+    borrow::Borrow,
+    ffi::{OsStr, OsString},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap_complete::{
+    CompletionCandidate,
+    env::{EnvCompleter, Fish, Shells, Zsh},
+};
+use rsdex_lib::{
+    pokedex::{PokeDexMmap, Pokedex},
+    reading::load_dataset,
+    search::SearchQueryParsing,
+    vocabulary::Vocabulary,
+};
+
+use crate::dataset::Dataset;
+
+///the environment variable the completion scripts set when they call rsdex
+pub const VAR: &str = "COMPLETE";
+pub const SHELLS: Shells<'static> = Shells(&[&Bash, &Zsh, &Fish]);
+
+///writes the script that has to be sourced for `shell`, it calls back into rsdex for every completion
+pub fn write_script(shell: &str, out: &mut dyn Write) -> io::Result<()> {
+    let shell = SHELLS
+        .completer(shell)
+        .ok_or_else(|| io::Error::other(format!("rsdex can't complete {shell}")))?;
+    shell.write_registration(VAR, "rsdex", "rsdex", "rsdex", out)
+}
+
+///completes the search keys and what goes after them, `type:fi` becomes `type:fire`
+///
+///the values come from the data set given with `--dataset` if there is one
+pub fn complete_query(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    suggest(current, || {
+        let pokedex = PokeDexMmap::new().ok()?;
+        //a data set that can't be read still gets the built in values
+        Some(
+            match dataset_path().map(|path| load_dataset(&path, &pokedex)) {
+                Some(Ok(loaded)) => Dataset::Loaded(loaded),
+                _ => Dataset::BuiltIn(pokedex),
+            },
+        )
+    })
+    .into_iter()
+    .map(|(option, amount)| {
+        CompletionCandidate::new(option)
+            .help(amount.map(|amount| format!("{amount} pokemon").into()))
+    })
+    .collect()
+}
+
+///what `current` can be completed to, values other than names come with how many pokemon have them
+///
+///`pokedex` is only called once a key has been typed
+pub fn suggest<P: Pokedex, B: Borrow<P>>(
+    current: &str,
+    pokedex: impl FnOnce() -> Option<B>,
+) -> Vec<(String, Option<usize>)> {
+    let Some(split) = current.find([':', '=']) else {
+        return SearchQueryParsing::KEYS
+            .iter()
+            .map(|key| format!("{key}:"))
+            .chain(["and".to_string(), "or".to_string()])
+            .filter(|option| option.starts_with(&current.to_lowercase()))
+            .map(|option| (option, None))
+            .collect();
+    };
+    let (key, value) = (&current[..=split], current[split + 1..].to_lowercase());
+    let Some(vocabulary) = SearchQueryParsing::from_str(&key[..split])
+        .ok()
+        .and_then(Vocabulary::from_search_key)
+    else {
+        return Vec::new();
+    };
+    let Some(pokedex) = pokedex() else {
+        return Vec::new();
+    };
+    vocabulary
+        .values(pokedex.borrow())
+        .into_iter()
+        .filter(|(option, _)| option.starts_with(&value))
+        .map(|(option, amount)| {
+            let amount = (vocabulary != Vocabulary::Names).then_some(amount);
+            (format!("{key}{option}"), amount)
+        })
+        .collect()
+}
+
+///the `--dataset` on the line being completed
+fn dataset_path() -> Option<PathBuf> {
+    //the line comes after `--`
+    let mut args = std::env::args_os().skip_while(|arg| arg != "--").skip(1);
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy();
+        if arg == "--dataset" || arg == "--data" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg
+            .strip_prefix("--dataset=")
+            .or_else(|| arg.strip_prefix("--data="))
+        {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+///the bash script from clap_complete gets the words from bash which splits `type:fire` at the `:`
+///so this one reads them from the line instead
+struct Bash;
+impl EnvCompleter for Bash {
+    fn name(&self) -> &'static str {
+        clap_complete::env::Bash.name()
+    }
+    fn is(&self, name: &str) -> bool {
+        clap_complete::env::Bash.is(name)
+    }
+    fn write_registration(
+        &self,
+        var: &str,
+        name: &str,
+        bin: &str,
+        completer: &str,
+        buf: &mut dyn Write,
+    ) -> io::Result<()> {
+        let script = r#"
+_NAME_complete() {
+    local IFS=$'\013'
+    local line="${COMP_LINE:0:COMP_POINT}"
+    local words
+    IFS=' ' read -ra words <<< "$line"
+    if [[ -z "$line" || "$line" == *' ' ]]; then
+        words+=("")
+    fi
+    local cur="${words[${#words[@]}-1]}"
+    COMPREPLY=( $( \
+        _CLAP_IFS="$IFS" \
+        _CLAP_COMPLETE_INDEX="$(( ${#words[@]} - 1 ))" \
+        VAR="bash" \
+        "COMPLETER" -- "${words[@]}" \
+    ) )
+    if [[ $? != 0 ]]; then
+        unset COMPREPLY
+        return
+    fi
+    # bash only replaces what comes after the last : or =
+    local prefix="${cur%"${cur##*[:=]}"}"
+    COMPREPLY=( "${COMPREPLY[@]#"$prefix"}" )
+    if [[ "${COMPREPLY-}" =~ [=/:]$ ]]; then
+        compopt -o nospace
+    fi
+}
+complete -o bashdefault -F _NAME_complete BIN
+"#
+        .replace("NAME", &name.replace('-', "_"))
+        .replace("BIN", bin)
+        .replace("COMPLETER", completer)
+        .replace("VAR", var);
+        writeln!(buf, "{script}")
+    }
+    fn write_complete(
+        &self,
+        cmd: &mut clap::Command,
+        args: Vec<OsString>,
+        current_dir: Option<&Path>,
+        buf: &mut dyn Write,
+    ) -> io::Result<()> {
+        clap_complete::env::Bash.write_complete(cmd, args, current_dir, buf)
+    }
+}
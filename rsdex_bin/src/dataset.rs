@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use rsdex_lib::{
+    Pokemon,
+    pokedex::{MultiSearchReturn, PokeDexMmap, Pokedex, SingleSearchReturn, VecPokedex},
+    reading::{ReadError, load_dataset},
+};
+
+///the built in pokedex or one loaded with `--dataset`
+pub enum Dataset {
+    BuiltIn(PokeDexMmap),
+    Loaded(VecPokedex),
+}
+impl Dataset {
+    ///fields missing from the file are filled in from the built in pokedex
+    pub fn load(built_in: PokeDexMmap, path: Option<&Path>) -> Result<Self, ReadError> {
+        match path {
+            Some(path) => Ok(Dataset::Loaded(load_dataset(path, &built_in)?)),
+            None => Ok(Dataset::BuiltIn(built_in)),
+        }
+    }
+}
+impl Pokedex for Dataset {
+    fn find_many_pokemon<P: Fn(&Pokemon) -> bool + Sync + Send>(
+        &self,
+        filter: P,
+    ) -> MultiSearchReturn {
+        match self {
+            Dataset::BuiltIn(dex) => dex.find_many_pokemon(filter),
+            Dataset::Loaded(dex) => dex.find_many_pokemon(filter),
+        }
+    }
+    fn find_single_pokemon<P: Fn(&Pokemon) -> bool + Sync + Send>(
+        &self,
+        find: P,
+    ) -> SingleSearchReturn {
+        match self {
+            Dataset::BuiltIn(dex) => dex.find_single_pokemon(find),
+            Dataset::Loaded(dex) => dex.find_single_pokemon(find),
+        }
+    }
+}
+
+///the built in data as a [`VecPokedex`] for tests, [`PokeDexMmap`] looks for it from where cargo is run
+#[cfg(test)]
+pub fn test_pokedex() -> VecPokedex {
+    let data = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../rsdex_lib/pokedex.jsonl"
+    ))
+    .unwrap();
+    VecPokedex::new(
+        data.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect(),
+    )
+}
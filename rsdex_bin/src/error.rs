@@ -0,0 +1,100 @@
+use std::{fmt::Display, io, process::ExitCode};
+
+use rsdex_lib::reading::ReadError;
+
+///why rsdex stopped, every kind exits with its own code so scripts can tell them apart
+///
+///the codes other than [`RsdexError::NoResults`] are the ones from `sysexits.h`
+#[derive(Debug)]
+pub enum RsdexError {
+    ///the search was fine but nothing matched
+    NoResults,
+    ///the arguments or the search don't make sense
+    Usage(String),
+    ///the built in data or a data set is broken
+    Data(String),
+    ///reading or writing a file, stdin or stdout failed
+    Io(String, io::Error),
+}
+impl RsdexError {
+    pub const NO_RESULTS: u8 = 1;
+    pub const USAGE: u8 = 64;
+    pub const DATA: u8 = 65;
+    pub const IO: u8 = 74;
+
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            RsdexError::NoResults => Self::NO_RESULTS,
+            RsdexError::Usage(_) => Self::USAGE,
+            RsdexError::Data(_) => Self::DATA,
+            RsdexError::Io(..) => Self::IO,
+        })
+    }
+    ///`what` says what was being done, like `could not create out.csv`
+    pub fn io(what: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let what = what.into();
+        move |e| RsdexError::Io(what, e)
+    }
+    ///for when printing the results fails, like on a full disk
+    pub fn stdout(e: io::Error) -> Self {
+        RsdexError::Io("could not write to stdout".into(), e)
+    }
+}
+impl Display for RsdexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RsdexError::NoResults => write!(f, "sorry we couldn't find any thing in our data"),
+            RsdexError::Usage(message) | RsdexError::Data(message) => write!(f, "{message}"),
+            RsdexError::Io(what, e) => write!(f, "{what}: {e}"),
+        }
+    }
+}
+impl From<ReadError> for RsdexError {
+    fn from(e: ReadError) -> Self {
+        match e {
+            ReadError::Io(e) => RsdexError::Io("could not read the data set".into(), e),
+            e => RsdexError::Data(format!("could not load the data set because: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, process::ExitCode};
+
+    use rsdex_lib::reading::ReadError;
+
+    use super::RsdexError;
+
+    #[test]
+    fn test_exit_codes() {
+        let io = || io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(RsdexError::NoResults.exit_code(), ExitCode::from(1));
+        assert_eq!(
+            RsdexError::Usage(String::new()).exit_code(),
+            ExitCode::from(64)
+        );
+        assert_eq!(
+            RsdexError::Data(String::new()).exit_code(),
+            ExitCode::from(65)
+        );
+        assert_eq!(
+            RsdexError::io("out.csv")(io()).exit_code(),
+            ExitCode::from(74)
+        );
+
+        assert!(matches!(
+            RsdexError::from(ReadError::Io(io())),
+            RsdexError::Io(..)
+        ));
+        let invalid = ReadError::Invalid {
+            record: 2,
+            message: "bad".into(),
+        };
+        assert!(matches!(RsdexError::from(invalid), RsdexError::Data(_)));
+        assert!(matches!(
+            RsdexError::from(ReadError::UnknownReadType),
+            RsdexError::Data(_)
+        ));
+    }
+}
@@ -1,134 +1,403 @@
-use std::{path::PathBuf, str::FromStr as _};
+use std::{
+    io::Read as _,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr as _,
+};
 
-use clap::{Parser, value_parser};
+use clap::{
+    CommandFactory as _, Parser,
+    builder::{PossibleValuesParser, TypedValueParser as _},
+    value_parser,
+};
+use clap_complete::{ArgValueCompleter, CompleteEnv};
 use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
 use rsdex_lib::{
+    Field, FieldSet,
+    color::ColorChoice,
     max_pokedex_number,
-    pokedex::{PokeDexMmap, Pokedex},
+    pokedex::{PokeDexMmap, Pokedex, PokedexSearchResult},
     search::KeyWord,
-    writing::WriteType,
+    summary::Summary,
+    vocabulary::Vocabulary,
+    writing::{SplitWriter, WriteType},
+};
+use strum::VariantNames as _;
+
+use crate::{
+    dataset::Dataset,
+    error::RsdexError,
+    output::{OutputFormat, View},
 };
 
-fn main() {
-    let args = RsdexArgs::parse();
+mod batch;
+mod completions;
+mod dataset;
+mod error;
+mod output;
+mod repl;
+mod rpc;
+mod serve;
+mod tui;
 
-    if let Some(other) = args.other {
-        match other {
-            OtherCommands::AmountOfPokemon => println!("{}", max_pokedex_number()),
+fn main() -> ExitCode {
+    //the completion scripts call rsdex with `COMPLETE` set
+    CompleteEnv::with_factory(RsdexArgs::command)
+        .var(completions::VAR)
+        .shells(completions::SHELLS)
+        .complete();
+    let args = match RsdexArgs::try_parse() {
+        Ok(args) => args,
+        //`--version` isn't an error
+        Err(e) if !e.use_stderr() => {
+            let _ = e.print();
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            let _ = e.print();
+            return ExitCode::from(RsdexError::USAGE);
         }
+    };
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        //`rsdex | head` closing stdout early isn't an error
+        Err(RsdexError::Io(_, e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            e.exit_code()
+        }
+    }
+}
 
-        return;
+fn run(args: RsdexArgs) -> Result<(), RsdexError> {
+    match &args.other {
+        Some(OtherCommands::AmountOfPokemon) => {
+            output::print(&format!("{}\n", max_pokedex_number())).map_err(RsdexError::stdout)?;
+            return Ok(());
+        }
+        Some(OtherCommands::Completions { shell }) => {
+            return completions::write_script(shell, &mut std::io::stdout())
+                .map_err(RsdexError::io("could not write the completions"));
+        }
+        _ => (),
+    }
+    if args.append && args.file_path.as_deref() == Some(Path::new("-")) {
+        return Err(RsdexError::Usage(
+            "--append needs a file, stdout can't be added to".into(),
+        ));
     }
 
-    let detail_level = args.detailed;
-    let pokedex = match PokeDexMmap::new() {
-        Ok(dex) => dex,
-        Err(e) => panic!("could not build pokedex because: {e}"),
-    };
+    let fields = args
+        .fields
+        .unwrap_or_else(|| FieldSet::from_detail_level(args.detailed));
+    let pokedex = PokeDexMmap::new()
+        .map_err(|e| RsdexError::Data(format!("could not read the built in data because: {e}")))?;
+    let pokedex = Dataset::load(pokedex, args.dataset.as_deref())?;
 
-    if args.help {
-        print_read_me();
-        return;
+    if let Some(OtherCommands::List { vocabulary }) = args.other {
+        output::print(&output::value_lines(&vocabulary.values(&pokedex)))
+            .map_err(RsdexError::stdout)?;
+        return Ok(());
     }
-
-    if args.search_queries.is_empty() {
-        println!("please add an argument or use --help for help");
-        return;
+    if let Some(OtherCommands::Info) = args.other {
+        output::print(&Summary::new(&pokedex).to_string()).map_err(RsdexError::stdout)?;
+        return Ok(());
+    }
+    if let Some(OtherCommands::Repl) = args.other {
+        return repl::run(&pokedex, fields, args.color)
+            .map_err(|e| RsdexError::Io("the repl stopped".into(), std::io::Error::other(e)));
+    }
+    if let Some(OtherCommands::Batch { queries }) = &args.other {
+        let view = match args.format.or(args.write_mode.map(OutputFormat::Write)) {
+            Some(format) => View::Format(format),
+            None if args.table => View::Table,
+            None if args.bars => View::Bars,
+            None => View::Auto,
+        };
+        let target =
+            batch::Target::new(args.file_path.as_deref(), view, fields.clone(), args.pretty)
+                .map_err(RsdexError::Usage)?;
+        let found = batch::run(&pokedex, queries, target, &fields, args.color)
+            .map_err(RsdexError::io("the batch stopped"))?;
+        return if found == 0 {
+            Err(RsdexError::NoResults)
+        } else {
+            Ok(())
+        };
+    }
+    if let Some(OtherCommands::Serve { host, port }) = &args.other {
+        return serve::run(&pokedex, host, *port).map_err(RsdexError::io("the server stopped"));
+    }
+    if let Some(OtherCommands::Rpc) = args.other {
+        return rpc::run(&pokedex).map_err(RsdexError::io("the rpc stopped"));
+    }
+    if let Some(OtherCommands::Tui) = args.other {
+        return tui::run(&pokedex, fields, args.detailed)
+            .map_err(RsdexError::io("the tui stopped"));
     }
 
-    let search_queries =
-        KeyWord::parse(&mut args.search_queries.into_iter()).expect("parsing failed");
-    let mut search_result = pokedex.search_many(search_queries);
+    if args.help {
+        output::print(&read_me()).map_err(RsdexError::stdout)?;
+        return Ok(());
+    }
 
-    if let Some(fp) = args.file_path {
-        // let fp = Path::new(&fp);
-        let file = std::fs::File::create(&fp)
-            .unwrap_or_else(|e| panic!("sorry rsdex could not create your file because {e}"));
+    let mut search_result = if args.stdin {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(RsdexError::io("could not read stdin"))?;
+        resolve_lines(&pokedex, &text)
+    } else {
+        if args.search_queries.is_empty() {
+            return Err(RsdexError::Usage(
+                "please add an argument or use --help for help".into(),
+            ));
+        }
+        let search_queries =
+            KeyWord::parse(&mut args.search_queries.into_iter()).map_err(RsdexError::Usage)?;
+        pokedex.search_many(search_queries)
+    };
+    if search_result.is_empty() {
+        return Err(RsdexError::NoResults);
+    }
 
-        let mut writer = std::io::BufWriter::new(file);
-        let mut write_mode = args.write_mode;
-        if write_mode.is_none() {
-            write_mode = match WriteType::from_str(
-                fp.extension()
-                    .unwrap_or_else(|| std::ffi::OsStr::new("extension missing"))
-                    .to_str()
-                    .expect("sorry the file path isn't valid unicode"),
-            ) {
-                Ok(w) => Some(w),
-                Err(_) => {
-                    println!("{:?}", std::io::Error::other("could not guess writemode"));
-                    return;
+    let format = args.format.or(args.write_mode.map(OutputFormat::Write));
+    if let Some(split_by) = args.split_by {
+        let write_type = match format {
+            Some(OutputFormat::Write(write_type)) => write_type,
+            Some(OutputFormat::Template(_)) => {
+                return Err(RsdexError::Usage(
+                    "--split-by needs a write type not a template".into(),
+                ));
+            }
+            None => WriteType::Csv,
+        };
+        let dir = args
+            .file_path
+            .ok_or_else(|| RsdexError::Usage("--split-by needs --file-path".into()))?;
+        let paths = SplitWriter::new(&dir, split_by, write_type, fields, args.pretty)
+            .write(&search_result)
+            .map_err(RsdexError::io(format!(
+                "sorry rsdex could not write your files to {}",
+                dir.display()
+            )))?;
+        output::print(&format!(
+            "wrote {} files to {}\n",
+            paths.len(),
+            dir.display()
+        ))
+        .map_err(RsdexError::stdout)?;
+        return Ok(());
+    }
+    match args.file_path {
+        Some(fp) if fp.as_os_str() != "-" => {
+            //`dex.jsonl.zst` is a zstd compressed jsonl file
+            let (compression, write_type) = output::split_path(&fp);
+            let format = match (format, write_type) {
+                (Some(format), _) => format,
+                (None, Some(write_type)) => OutputFormat::Write(write_type),
+                (None, None) => {
+                    return Err(RsdexError::Usage(format!(
+                        "could not guess the write mode from {}, use --write-mode",
+                        fp.display()
+                    )));
                 }
+            };
+            if args.append && !format.can_append() {
+                return Err(RsdexError::Usage(
+                    "only jsonl, csv and --format templates can be appended to".into(),
+                ));
             }
+            let mut file = if args.append {
+                std::fs::OpenOptions::new()
+                    .read(true)
+                    .append(true)
+                    .create(true)
+                    .open(&fp)
+            } else {
+                std::fs::File::create(&fp)
+            }
+            .map_err(RsdexError::io(format!(
+                "sorry rsdex could not create {}",
+                fp.display()
+            )))?;
+            let appending = if args.append && file.metadata().is_ok_and(|meta| meta.len() > 0) {
+                //compressed files can't be looked into, rsdex doesn't end them with a new line
+                Some(
+                    compression.is_none()
+                        && output::ends_with_new_line(&mut file).map_err(RsdexError::io(
+                            format!("sorry rsdex could not read {}", fp.display()),
+                        ))?,
+                )
+            } else {
+                None
+            };
+
+            output::write_file(
+                file,
+                compression,
+                &search_result,
+                format.writer(fields, args.pretty, appending).as_mut(),
+            )
+            .map_err(RsdexError::io(format!(
+                "something went wrong while saving {}",
+                fp.display()
+            )))?;
+            output::print("writing successful\n").map_err(RsdexError::stdout)?;
         }
-        search_result
-            .write_data(
-                &mut writer,
-                detail_level,
-                write_mode.expect("invailed write_mode state: still None"),
-                args.pretty,
+        //`-p -` or a write type without a path goes to stdout
+        file_path if file_path.is_some() || matches!(format, Some(OutputFormat::Write(_))) => {
+            let format = format.unwrap_or(OutputFormat::Write(WriteType::default()));
+            output::write_stdout(
+                &search_result,
+                format.writer(fields, args.pretty, None).as_mut(),
             )
-            .expect("something went wrong while saving your file");
-        println!("writing successful")
-    } else {
-        search_result.sort();
-        search_result.print_data(detail_level);
+            .map_err(RsdexError::io("something went wrong while writing"))?;
+        }
+        _ => {
+            let view = match format {
+                Some(format) => View::Format(format),
+                None if args.table => View::Table,
+                None if args.bars => View::Bars,
+                None => View::Auto,
+            };
+            //names from stdin stay in the order they were given
+            if !args.stdin {
+                search_result.sort();
+            }
+            output::show(&search_result, &view, &fields, args.color).map_err(RsdexError::stdout)?;
+        }
     }
+    Ok(())
+}
+
+///every line as a name or dex number in the order they were given, lines that aren't a pokemon are reported
+fn resolve_lines<P: Pokedex>(pokedex: &P, text: &str) -> PokedexSearchResult {
+    let pokemon = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(number, line)| match pokedex.resolve(line) {
+            Ok(pokemon) => Some(pokemon),
+            Err(e) => {
+                eprintln!("line {}: {e}", number + 1);
+                None
+            }
+        })
+        .collect();
+    PokedexSearchResult::new(pokemon)
 }
 
 #[derive(clap::Parser)]
 #[command(version, disable_help_flag = true)]
 struct RsdexArgs {
+    #[arg(add = ArgValueCompleter::new(completions::complete_query))]
     search_queries: Vec<String>,
-    #[arg(long, short,value_parser = value_parser!(u8).range(0..=5),default_value_t=0)]
+    #[arg(long, short,value_parser = value_parser!(u8).range(0..=4),default_value_t=0)]
     detailed: u8,
+    #[arg(long, short, conflicts_with = "detailed")]
+    fields: Option<FieldSet>,
     #[arg(long, aliases(["fp","filepath"]),short('p'))]
     file_path: Option<PathBuf>,
-    #[arg(long, requires = "file_path",aliases(["mode"]))]
+    #[arg(long, aliases(["mode"]))]
     write_mode: Option<WriteType>,
-    #[arg(long, requires = "file_path")]
+    #[arg(long)]
     pretty: bool,
+    ///writes one file per value of the field into the directory given with `--file-path`
+    #[arg(long, requires = "file_path", conflicts_with = "append")]
+    split_by: Option<Field>,
+    ///adds to the end of a jsonl or csv file instead of replacing it
+    #[arg(long, requires = "file_path")]
+    append: bool,
+    ///a json, jsonl or csv file written by rsdex to search instead of the built in data
+    #[arg(long, aliases(["data"]))]
+    dataset: Option<PathBuf>,
     #[arg(long, short, exclusive(true))]
     help: bool,
+    ///reads names or dex numbers one per line from stdin instead of searching
+    #[arg(long, conflicts_with = "search_queries")]
+    stdin: bool,
+    #[arg(long, short, conflicts_with = "file_path")]
+    table: bool,
+    #[arg(long, short, conflicts_with_all = ["file_path", "table"])]
+    bars: bool,
+    #[arg(long, conflicts_with_all = ["write_mode", "table", "bars"])]
+    format: Option<OutputFormat>,
+    #[arg(long, default_value_t = ColorChoice::Auto, value_parser = PossibleValuesParser::new(ColorChoice::VARIANTS)
+        .map(|choice| ColorChoice::from_str(&choice).expect("only possible values get through")))]
+    color: ColorChoice,
     #[command(subcommand)]
     other: Option<OtherCommands>,
 }
 include!(concat!(env!("OUT_DIR"), "/readme.rs"));
-fn print_read_me() {
+///the readme with the markdown turned into terminal escapes
+fn read_me() -> String {
     let parser = pulldown_cmark::Parser::new(READ_ME);
     let mut list = false;
+    let mut text = String::new();
     for event in parser {
         match event {
-            Event::SoftBreak => println!(),
-            Event::HardBreak => println!(),
-            Event::Code(code) => print!("\x1b[48;5;235m{code}\x1b[0m"),
-            Event::Text(text) => {
+            Event::SoftBreak => text.push('\n'),
+            Event::HardBreak => text.push('\n'),
+            Event::Code(code) => text += &format!("\x1b[48;5;235m{code}\x1b[0m"),
+            Event::Text(line) => {
                 if list {
-                    println!("* {text}")
+                    text += &format!("* {line}\n")
                 } else {
-                    print!("{text}")
+                    text += &line
                 }
             }
             //double new lines is intentional
             Event::Start(Tag::Heading { level, .. }) => match level {
                 //bold
-                HeadingLevel::H3 => print!("\n\n\x1B[1m"),
+                HeadingLevel::H3 => text += "\n\n\x1B[1m",
                 //bold and underline
-                HeadingLevel::H2 => print!("\n\n\x1B[1;4m"),
+                HeadingLevel::H2 => text += "\n\n\x1B[1;4m",
                 _ => (),
             },
             Event::Start(Tag::List(..)) => list = true,
             Event::End(TagEnd::List(..)) => list = false,
-            Event::End(TagEnd::Heading(_)) => println!("\x1b[0m\n"),
+            Event::End(TagEnd::Heading(_)) => text += "\x1b[0m\n\n",
             _ => (),
         }
     }
-    println!()
+    text + "\n"
 }
 #[derive(clap::clap_derive::Subcommand, Clone)]
 enum OtherCommands {
     #[command(name = "amount", alias = "amount_of_pokemon")]
     AmountOfPokemon,
+    ///searches line by line with the data set kept loaded
+    Repl,
+    ///how many pokemon the data set has, gaps in the dex numbers and other things to check it with
+    Info,
+    ///a full screen browser that searches as you type
+    Tui,
+    ///every value of something with how many pokemon have it
+    List {
+        #[arg(value_parser = PossibleValuesParser::new(Vocabulary::VARIANTS)
+            .map(|vocabulary| Vocabulary::from_str(&vocabulary).expect("only possible values get through")))]
+        vocabulary: Vocabulary,
+    },
+    ///runs every line of a file as its own search, `-` reads the lines from stdin
+    Batch { queries: PathBuf },
+    ///answers lookups and searches over http with json
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        ///`0.0.0.0` makes it reachable from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+    ///answers json-rpc 2.0 requests one per line on stdin, for editors and bots
+    Rpc,
+    ///prints the script that has to be sourced for tab completion
+    Completions {
+        #[arg(value_parser = PossibleValuesParser::new(completions::SHELLS.names()))]
+        shell: String,
+    },
 }
 // #[command(group(ArgGroup::new("others").args(["amount_of_pokemon","test"])))]
 // struct OtherCommands{
@@ -0,0 +1,336 @@
+use std::{
+    io::{IsTerminal, Read, Seek, SeekFrom, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use rsdex_lib::{
+    FieldSet, PokemonWriter, Template,
+    color::{ColorChoice, Painter},
+    pokedex::PokedexSearchResult,
+    table::render_table,
+    writing::{Compression, WriteType},
+};
+use strum::VariantNames as _;
+
+///how results get written, either a [`WriteType`] or a [`Template`]
+#[derive(Clone)]
+pub enum OutputFormat {
+    Write(WriteType),
+    Template(Template),
+}
+impl OutputFormat {
+    ///`appending` is there when a file is added to and says if it already ends with a new line
+    pub fn writer(
+        self,
+        fields: FieldSet,
+        pretty: bool,
+        appending: Option<bool>,
+    ) -> Box<dyn PokemonWriter> {
+        match (self, appending) {
+            (OutputFormat::Write(write_type), Some(ends_with_new_line)) => write_type
+                .append_writer(fields, pretty, ends_with_new_line)
+                .expect("checked with can_append"),
+            (OutputFormat::Write(write_type), None) => write_type.writer(fields, pretty),
+            (OutputFormat::Template(template), _) => Box::new(template),
+        }
+    }
+    pub fn can_append(&self) -> bool {
+        match self {
+            OutputFormat::Write(write_type) => write_type.can_append(),
+            OutputFormat::Template(_) => true,
+        }
+    }
+}
+impl FromStr for OutputFormat {
+    type Err = String;
+    ///anything with a `{` in it is a template
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('{') {
+            return Template::from_str(s).map(OutputFormat::Template);
+        }
+        WriteType::from_str(s)
+            .map(OutputFormat::Write)
+            .map_err(|_| format!("'{s}' is not a write type or a template"))
+    }
+}
+
+///how results are shown when they aren't written to a file
+#[derive(Clone)]
+pub enum View {
+    ///a table if there is more than one result and the output is a terminal
+    Auto,
+    Table,
+    List,
+    Bars,
+    Format(OutputFormat),
+}
+impl View {
+    ///what `:format` and `--format` take other than templates
+    pub fn names() -> Vec<String> {
+        ["auto", "table", "list", "bars"]
+            .into_iter()
+            .map(String::from)
+            .chain(WriteType::VARIANTS.iter().map(|name| name.to_lowercase()))
+            .collect()
+    }
+}
+impl FromStr for View {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(View::Auto),
+            "table" => Ok(View::Table),
+            "list" => Ok(View::List),
+            "bars" => Ok(View::Bars),
+            format => OutputFormat::from_str(format).map(View::Format),
+        }
+    }
+}
+
+///prints the results the way `view` says
+///
+///like [`print`] a closed pipe isn't an error, no results are reported on stderr
+pub fn show(
+    search_result: &PokedexSearchResult,
+    view: &View,
+    fields: &FieldSet,
+    color: ColorChoice,
+) -> std::io::Result<()> {
+    if search_result.is_empty() {
+        eprintln!("sorry we couldn't find any thing in our data");
+    }
+    print(&render(search_result, view, fields, color))
+}
+///what [`show`] prints, nothing when there are no results
+pub fn render(
+    search_result: &PokedexSearchResult,
+    view: &View,
+    fields: &FieldSet,
+    color: ColorChoice,
+) -> String {
+    let is_terminal = std::io::stdout().is_terminal();
+    let painter = Painter::new(color.enabled(is_terminal));
+    match view {
+        _ if search_result.is_empty() => String::new(),
+        View::Format(OutputFormat::Template(template)) => search_result
+            .pokemon()
+            .iter()
+            .map(|pokemon| template.render(pokemon) + "\n")
+            .collect(),
+        View::Format(format) => {
+            let mut out = Vec::new();
+            if let Err(e) = search_result.write_data(
+                &mut out,
+                format.clone().writer(fields.clone(), false, None).as_mut(),
+            ) {
+                eprintln!("something went wrong while writing: {e}")
+            }
+            String::from_utf8_lossy(&out).into_owned()
+        }
+        View::Table => render_table_view(search_result, fields, is_terminal, &painter),
+        View::Auto if is_terminal && search_result.len() > 1 => {
+            render_table_view(search_result, fields, is_terminal, &painter)
+        }
+        View::Bars => search_result.render_data(fields, &painter, true) + "\n",
+        _ => search_result.render_data(fields, &painter, false) + "\n",
+    }
+}
+fn render_table_view(
+    search_result: &PokedexSearchResult,
+    fields: &FieldSet,
+    is_terminal: bool,
+    painter: &Painter,
+) -> String {
+    let max_width = is_terminal.then(terminal_width).flatten();
+    render_table(search_result.pokemon(), fields, max_width, painter)
+}
+
+///the compression and write type a path asks for, `dex.jsonl.zst` is zstd compressed jsonl
+pub fn split_path(path: &Path) -> (Option<Compression>, Option<WriteType>) {
+    let (compression, inner_path) = match Compression::from_path(path) {
+        Some((compression, inner_path)) => (Some(compression), inner_path),
+        None => (None, path.to_path_buf()),
+    };
+    let write_type = inner_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(|extension| WriteType::from_str(extension).ok());
+    (compression, write_type)
+}
+
+pub fn write_file(
+    file: std::fs::File,
+    compression: Option<Compression>,
+    search_result: &PokedexSearchResult,
+    pokemon_writer: &mut dyn PokemonWriter,
+) -> std::io::Result<()> {
+    let mut writer = std::io::BufWriter::new(file);
+    match compression {
+        Some(compression) => {
+            let mut compressed = compression.writer(&mut writer)?;
+            search_result.write_data(&mut compressed, pokemon_writer)?;
+            compressed.finish()?;
+        }
+        None => search_result.write_data(&mut writer, pokemon_writer)?,
+    }
+    writer.flush()
+}
+
+///whether the last byte of a file that isn't empty is a new line
+pub fn ends_with_new_line<F: Read + Seek>(file: &mut F) -> std::io::Result<bool> {
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0];
+    file.read_exact(&mut last)?;
+    Ok(last == *b"\n")
+}
+
+///writes the results to `path` in the format its extension asks for
+pub fn save(
+    path: &Path,
+    search_result: &PokedexSearchResult,
+    fields: &FieldSet,
+) -> Result<(), String> {
+    let (compression, write_type) = split_path(path);
+    let write_type = write_type.ok_or("could not guess the write mode from the extension")?;
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    write_file(
+        file,
+        compression,
+        search_result,
+        write_type.writer(fields.clone(), false).as_mut(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+///a closed pipe like `rsdex ... | head` isn't treated as an error
+pub fn write_stdout(
+    search_result: &PokedexSearchResult,
+    pokemon_writer: &mut dyn PokemonWriter,
+) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match search_result
+        .write_data(&mut stdout, pokemon_writer)
+        .and_then(|_| stdout.flush())
+    {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        written => written,
+    }
+}
+
+///prints `text` as is, for output that is often piped into `head` or `grep`
+///
+///a closed pipe isn't an error, a full disk is
+pub fn print(text: &str) -> std::io::Result<()> {
+    write_text(&mut std::io::stdout().lock(), text)
+}
+fn write_text(out: &mut impl Write, text: &str) -> std::io::Result<()> {
+    match out.write_all(text.as_bytes()).and_then(|_| out.flush()) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        written => written,
+    }
+}
+
+///one `value  amount` line per value with the amounts lined up
+pub fn value_lines(values: &[(String, usize)]) -> String {
+    let width = values
+        .iter()
+        .map(|(value, _)| value.chars().count())
+        .max()
+        .unwrap_or(0);
+    values
+        .iter()
+        .map(|(value, amount)| format!("{value:<width$}  {amount}\n"))
+        .collect()
+}
+
+///the width of the terminal, `COLUMNS` is used if it cant be asked
+pub fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size()
+        .map(|(width, _)| width.0 as usize)
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Cursor, Seek, SeekFrom, Write},
+        str::FromStr,
+    };
+
+    use rsdex_lib::{
+        color::ColorChoice,
+        pokedex::{Pokedex, PokedexSearchResult},
+        writing::WriteType,
+    };
+
+    use super::{OutputFormat, View, ends_with_new_line, render, value_lines, write_text};
+    use crate::dataset::test_pokedex;
+
+    #[test]
+    fn test_value_lines() {
+        let values = [("fire".to_string(), 81), ("pokémon".to_string(), 3)];
+        assert_eq!(value_lines(&values), "fire     81\npokémon  3\n");
+        assert_eq!(value_lines(&[]), "");
+    }
+    #[test]
+    fn test_append_to_file_ending_in_new_line() {
+        let raichu = PokedexSearchResult::new(vec![test_pokedex().resolve("raichu").unwrap()]);
+        let append = |existing: &str| {
+            let mut file = Cursor::new(existing.as_bytes().to_vec());
+            let ends_with_new_line = ends_with_new_line(&mut file).unwrap();
+            file.seek(SeekFrom::End(0)).unwrap();
+            raichu
+                .write_data(
+                    &mut file,
+                    OutputFormat::Write(WriteType::Jsonl)
+                        .writer(0.into(), false, Some(ends_with_new_line))
+                        .as_mut(),
+                )
+                .unwrap();
+            file.flush().unwrap();
+            String::from_utf8(file.into_inner()).unwrap()
+        };
+        let written = r#"{"name":"Raichu","national dex number":"26"}"#;
+        assert_eq!(append("{}\n"), format!("{{}}\n{written}"));
+        assert_eq!(append("{}"), format!("{{}}\n{written}"));
+    }
+    #[test]
+    fn test_render_nothing_for_no_results() {
+        let empty = PokedexSearchResult::new(Vec::new());
+        for name in View::names() {
+            let view = View::from_str(&name).unwrap();
+            assert_eq!(render(&empty, &view, &0.into(), ColorChoice::Never), "");
+        }
+    }
+    #[test]
+    fn test_write_text_errors() {
+        struct Broken(io::ErrorKind);
+        impl Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(self.0.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut out = Vec::new();
+        write_text(&mut out, "pikachu\n").unwrap();
+        assert_eq!(out, b"pikachu\n");
+        assert!(write_text(&mut Broken(io::ErrorKind::BrokenPipe), "pikachu\n").is_ok());
+        let full = write_text(&mut Broken(io::ErrorKind::StorageFull), "pikachu\n");
+        assert_eq!(full.unwrap_err().kind(), io::ErrorKind::StorageFull);
+    }
+    #[test]
+    fn test_view_names() {
+        let names = View::names();
+        for name in ["auto", "bars", "jsonl", "markdown", "sqlite"] {
+            assert!(names.iter().any(|view| view == name), "{name} is missing");
+        }
+        #[cfg(feature = "columnar")]
+        assert!(names.iter().any(|view| view == "parquet"));
+        for name in names {
+            assert!(View::from_str(&name).is_ok(), "{name} doesn't parse");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{path::PathBuf, str::FromStr};
+
+use rsdex_lib::{
+    FieldSet,
+    color::ColorChoice,
+    pokedex::{Pokedex, PokedexSearchResult},
+    search::{KeyWord, SearchQueryParsing},
+    vocabulary::Vocabulary,
+};
+use rustyline::{
+    Context, Editor, Helper,
+    completion::{Completer, FilenameCompleter, Pair},
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    history::DefaultHistory,
+    validate::Validator,
+};
+use strum::IntoEnumIterator;
+
+use crate::output::{self, OutputFormat, View};
+
+const COMMANDS: [&str; 6] = [":help", ":detail", ":fields", ":format", ":save", ":quit"];
+const HELP: &str = "type a search like `type:fire and stat:g100s` or one of these commands
+:detail <0-4>     change the detail level
+:fields <fields>  pick the fields like --fields
+:format <format>  auto, table, list, bars, a write type like json or a --format template
+:save <file>      write the last results to a file, the format comes from the extension
+:quit             leave, ctrl+d works too";
+
+///the settings that `:` commands change
+struct Session {
+    fields: FieldSet,
+    view: View,
+    color: ColorChoice,
+    last: Option<PokedexSearchResult>,
+}
+
+///keeps `pokedex` loaded and runs searches line by line until `:quit`
+pub fn run<P: Pokedex>(pokedex: &P, fields: FieldSet, color: ColorChoice) -> rustyline::Result<()> {
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper::new(pokedex)));
+    let history = history_path();
+    if let Some(history) = &history {
+        //there is no history the first time
+        let _ = editor.load_history(history);
+    }
+    let mut session = Session {
+        fields,
+        view: View::Auto,
+        color,
+        last: None,
+    };
+    output::print("rsdex repl, :help for help\n")?;
+    loop {
+        let line = match editor.readline("rsdex> ") {
+            Ok(line) => line,
+            //ctrl+c clears the line
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        if let Some(command) = line.strip_prefix(':') {
+            let (command, value) = command.split_once(' ').unwrap_or((command, ""));
+            match run_command(&mut session, command, value.trim()) {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(e) => eprintln!("{e}"),
+            }
+            continue;
+        }
+        match KeyWord::parse(&mut line.split_whitespace().map(String::from)) {
+            Ok(keyword) => {
+                let mut result = pokedex.search_many(keyword);
+                result.sort();
+                output::show(&result, &session.view, &session.fields, session.color)?;
+                //json and such don't end with a new line so the prompt would be stuck to them
+                if matches!(session.view, View::Format(OutputFormat::Write(_))) {
+                    output::print("\n")?;
+                }
+                session.last = Some(result);
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    if let Some(history) = &history {
+        if let Some(dir) = history.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        editor.save_history(history)?;
+    }
+    Ok(())
+}
+
+///returns false when the repl should stop
+fn run_command(session: &mut Session, command: &str, value: &str) -> Result<bool, String> {
+    match command {
+        "help" | "h" => output::print(&format!("{HELP}\n")).map_err(|e| e.to_string())?,
+        "quit" | "q" | "exit" => return Ok(false),
+        "detail" | "d" => {
+            let level = value
+                .parse::<u8>()
+                .ok()
+                .filter(|level| *level <= 4)
+                .ok_or("the detail level has to be between 0 and 4")?;
+            session.fields = FieldSet::from_detail_level(level);
+        }
+        "fields" | "f" => session.fields = FieldSet::from_str(value)?,
+        "format" => session.view = View::from_str(value)?,
+        "save" | "s" => {
+            let Some(result) = &session.last else {
+                return Err("search for something first".into());
+            };
+            if value.is_empty() {
+                return Err("where should it be saved, like :save fire.csv".into());
+            }
+            output::save(value.as_ref(), result, &session.fields)?;
+            output::print(&format!("saved {} pokemon to {value}\n", result.len()))
+                .map_err(|e| e.to_string())?;
+        }
+        other => return Err(format!("unknown command ':{other}' try :help")),
+    }
+    Ok(true)
+}
+
+///`$XDG_STATE_HOME/rsdex/history` or `~/.local/state/rsdex/history`
+fn history_path() -> Option<PathBuf> {
+    let state = std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|home| home.join(".local").join("state")))?;
+    Some(state.join("rsdex").join("history"))
+}
+
+///completes the search keys, `:` commands and the values that go after a key
+struct ReplHelper {
+    ///worked out once since the data set doesn't change
+    values: Vec<(SearchQueryParsing, Vec<String>)>,
+    files: FilenameCompleter,
+}
+impl ReplHelper {
+    fn new<P: Pokedex>(pokedex: &P) -> Self {
+        let values = Vocabulary::iter()
+            .filter_map(|vocabulary| {
+                let values = vocabulary.values(pokedex).into_iter();
+                Some((
+                    vocabulary.search_key()?,
+                    values.map(|(value, _)| value).collect(),
+                ))
+            })
+            .collect();
+        Self {
+            values,
+            files: FilenameCompleter::new(),
+        }
+    }
+    fn values_for(&self, key: &str) -> Vec<String> {
+        let Ok(key) = SearchQueryParsing::from_str(key) else {
+            return Vec::new();
+        };
+        self.values
+            .iter()
+            .find(|(other, _)| *other == key)
+            .map(|(_, values)| values.clone())
+            .unwrap_or_default()
+    }
+}
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        if before.starts_with(":save ") || before.starts_with(":s ") {
+            return self.files.complete(line, pos, ctx);
+        }
+        let start = before.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = &before[start..];
+        let (start, prefix, options) = if start == 0 && word.starts_with(':') {
+            (
+                start,
+                word,
+                COMMANDS.iter().map(|command| command.to_string()).collect(),
+            )
+        } else if before.starts_with(":format ") {
+            (start, word, View::names())
+        } else if before.starts_with(':') {
+            return Ok((start, Vec::new()));
+        } else if let Some(split) = word.find([':', '=']) {
+            let start = start + split + 1;
+            (start, &word[split + 1..], self.values_for(&word[..split]))
+        } else {
+            let mut options: Vec<String> = SearchQueryParsing::KEYS
+                .iter()
+                .map(|key| format!("{key}:"))
+                .collect();
+            options.extend(["and".to_string(), "or".to_string()]);
+            (start, word, options)
+        };
+        let prefix = prefix.to_lowercase();
+        let candidates = options
+            .into_iter()
+            .filter(|option| option.to_lowercase().starts_with(&prefix))
+            .map(|option| Pair {
+                display: option.clone(),
+                replacement: option,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+impl Highlighter for ReplHelper {}
+impl Validator for ReplHelper {}
+impl Helper for ReplHelper {}
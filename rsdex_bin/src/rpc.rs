@@ -0,0 +1,231 @@
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use rsdex_lib::{pokedex::Pokedex, search::KeyWord, vocabulary::Vocabulary};
+use serde_json::{Value, json};
+use strum::VariantNames as _;
+
+use crate::completions;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+///a pokemon that doesn't exist or a search without results, a search that doesn't parse is [`INVALID_PARAMS`]
+const NOT_FOUND: i64 = -32000;
+const INTERNAL_ERROR: i64 = -32603;
+
+type RpcResult = Result<Value, (i64, String)>;
+
+///answers json-rpc 2.0 requests, one per line on stdin, with one line each on stdout until stdin is closed
+///
+///the methods are `search`, `get`, `list_vocabulary` and `suggest`
+pub fn run<P: Pokedex>(pokedex: &P) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(Value::Array(requests)) if !requests.is_empty() => {
+                let responses: Vec<Value> = requests
+                    .iter()
+                    .filter_map(|request| answer(pokedex, request))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Ok(Value::Array(_)) => Some(response(
+                Value::Null,
+                Err((INVALID_REQUEST, "the batch is empty".into())),
+            )),
+            Ok(request) => answer(pokedex, &request),
+            Err(e) => Some(response(Value::Null, Err((PARSE_ERROR, e.to_string())))),
+        };
+        //notifications don't get an answer
+        if let Some(response) = response {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+///the response to one request, none if it is a notification
+fn answer<P: Pokedex>(pokedex: &P, request: &Value) -> Option<Value> {
+    let Some(method) = request
+        .get("method")
+        .and_then(Value::as_str)
+        .filter(|_| request.get("jsonrpc").and_then(Value::as_str) == Some("2.0"))
+    else {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        return Some(response(
+            id,
+            Err((INVALID_REQUEST, "expected jsonrpc 2.0 and a method".into())),
+        ));
+    };
+    let id = request.get("id")?.clone();
+    let params = request.get("params").unwrap_or(&Value::Null);
+    let result = match method {
+        "search" => search(pokedex, params),
+        "get" => get(pokedex, params),
+        "list_vocabulary" => list_vocabulary(pokedex, params),
+        "suggest" => suggest(pokedex, params),
+        method => Err((METHOD_NOT_FOUND, format!("there is no method '{method}'"))),
+    };
+    Some(response(id, result))
+}
+
+fn response(id: Value, result: RpcResult) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => {
+            json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+        }
+    }
+}
+
+///a param given by name or as the first of a list
+fn param<'a>(params: &'a Value, name: &str) -> Result<&'a Value, (i64, String)> {
+    match params {
+        Value::Object(params) => params.get(name),
+        Value::Array(params) => params.first(),
+        _ => None,
+    }
+    .ok_or_else(|| (INVALID_PARAMS, format!("missing the param '{name}'")))
+}
+fn string_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, (i64, String)> {
+    param(params, name)?
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, format!("'{name}' has to be a string")))
+}
+
+///`{"query": "type:fire and stat:g100s"}` gives the pokemon sorted by dex number
+fn search<P: Pokedex>(pokedex: &P, params: &Value) -> RpcResult {
+    let query = string_param(params, "query")?;
+    let keyword = KeyWord::parse(&mut query.split_whitespace().map(String::from))
+        .map_err(|e| (INVALID_PARAMS, e))?;
+    let mut result = pokedex.search_many(keyword);
+    if result.is_empty() {
+        return Err((NOT_FOUND, "no pokemon matched the search".into()));
+    }
+    result.sort();
+    serde_json::to_value(result.pokemon()).map_err(internal)
+}
+
+///`{"pokemon": 25}` or `{"pokemon": "mr-mime"}`
+fn get<P: Pokedex>(pokedex: &P, params: &Value) -> RpcResult {
+    let pokemon = match param(params, "pokemon")? {
+        Value::String(pokemon) => pokemon.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => {
+            return Err((
+                INVALID_PARAMS,
+                "'pokemon' has to be a name or a number".into(),
+            ));
+        }
+    };
+    let pokemon = pokedex.resolve(&pokemon).map_err(|e| (NOT_FOUND, e))?;
+    serde_json::to_value(pokemon).map_err(internal)
+}
+
+///`{"vocabulary": "types"}` gives every value with how many pokemon have it
+fn list_vocabulary<P: Pokedex>(pokedex: &P, params: &Value) -> RpcResult {
+    let name = string_param(params, "vocabulary")?;
+    let vocabulary = Vocabulary::from_str(name).map_err(|_| {
+        (
+            INVALID_PARAMS,
+            format!("'{name}' is not one of {}", Vocabulary::VARIANTS.join(", ")),
+        )
+    })?;
+    Ok(vocabulary
+        .values(pokedex)
+        .into_iter()
+        .map(|(value, amount)| json!({ "value": value, "amount": amount }))
+        .collect())
+}
+
+///`{"prefix": "type:fi"}` gives what the prefix can be completed to like tab completion does
+fn suggest<P: Pokedex>(pokedex: &P, params: &Value) -> RpcResult {
+    let prefix = string_param(params, "prefix")?;
+    Ok(completions::suggest::<P, _>(prefix, || Some(pokedex))
+        .into_iter()
+        .map(|(value, amount)| json!({ "value": value, "amount": amount }))
+        .collect())
+}
+
+fn internal(e: serde_json::Error) -> (i64, String) {
+    (INTERNAL_ERROR, e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{INVALID_PARAMS, METHOD_NOT_FOUND, NOT_FOUND, answer};
+    use crate::dataset::test_pokedex;
+
+    fn error_code(response: Option<Value>) -> Option<i64> {
+        response?["error"]["code"].as_i64()
+    }
+
+    #[test]
+    fn test_answer() {
+        let dex = test_pokedex();
+        let call = |method, params| {
+            answer(
+                &dex,
+                &json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+            )
+        };
+
+        let search = call("search", json!({ "query": "type:fire and type:water" })).unwrap();
+        assert_eq!(search["id"], 1);
+        assert_eq!(search["result"][0]["name"], "Volcanion");
+        let search = call("search", json!(["name:pikachu"])).unwrap();
+        assert_eq!(search["result"].as_array().map(Vec::len), Some(1));
+        let nothing = json!({ "query": "name:pikachu and name:bulbasaur" });
+        assert_eq!(error_code(call("search", nothing)), Some(NOT_FOUND));
+
+        assert_eq!(
+            call("get", json!({ "pokemon": 25 })).unwrap()["result"]["name"],
+            "Pikachu"
+        );
+        assert_eq!(
+            call("get", json!(["mr-mime"])).unwrap()["result"]["national_dex_number"],
+            122
+        );
+        assert_eq!(
+            error_code(call("get", json!({ "pokemon": "pikachoo" }))),
+            Some(NOT_FOUND)
+        );
+
+        assert_eq!(
+            error_code(call("evolve", json!({}))),
+            Some(METHOD_NOT_FOUND)
+        );
+
+        assert_eq!(error_code(call("search", json!({}))), Some(INVALID_PARAMS));
+        assert_eq!(
+            error_code(call("search", json!({ "query": 1 }))),
+            Some(INVALID_PARAMS)
+        );
+        assert_eq!(
+            error_code(call("search", json!({ "query": "typ:fire" }))),
+            Some(INVALID_PARAMS)
+        );
+        assert_eq!(
+            error_code(call("get", json!({ "pokemon": true }))),
+            Some(INVALID_PARAMS)
+        );
+        assert_eq!(
+            error_code(call("list_vocabulary", json!(["moves"]))),
+            Some(INVALID_PARAMS)
+        );
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "search", "params": ["type:fire"] });
+        assert_eq!(answer(&dex, &notification), None);
+    }
+}
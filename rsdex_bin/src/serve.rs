@@ -0,0 +1,118 @@
+use std::io;
+
+use percent_encoding::percent_decode_str;
+use rsdex_lib::{pokedex::Pokedex, search::KeyWord};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::output;
+
+///how many requests are answered at the same time
+const WORKERS: usize = 4;
+
+///answers `GET /pokemon/{name|number}` and `GET /search?q=...` with json until it is stopped
+///
+///every worker shares the one `pokedex`
+pub fn run<P: Pokedex + Sync>(pokedex: &P, host: &str, port: u16) -> io::Result<()> {
+    let server = Server::http((host, port)).map_err(io::Error::other)?;
+    output::print(&format!("listening on http://{host}:{port}\n"))?;
+    std::thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    let (status, body) = answer(pokedex, request.method(), request.url());
+                    respond(request, status, body);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+///the status and the json body for a request
+fn answer<P: Pokedex>(pokedex: &P, method: &Method, url: &str) -> (u16, String) {
+    if *method != Method::Get {
+        return error(405, "only GET is supported");
+    }
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    if let Some(pokemon) = path.strip_prefix("/pokemon/") {
+        let pokemon = percent_decode_str(pokemon).decode_utf8_lossy();
+        return match pokedex.resolve(&pokemon) {
+            Ok(pokemon) => json(&pokemon),
+            Err(e) => error(404, &e),
+        };
+    }
+    if path != "/search" {
+        return error(
+            404,
+            "there is only /pokemon/{name|number} and /search?q=...",
+        );
+    }
+    let Some((_, search)) = form_urlencoded::parse(query.as_bytes()).find(|(key, _)| key == "q")
+    else {
+        return error(400, "the search goes in ?q=, like ?q=type:fire");
+    };
+    match KeyWord::parse(&mut search.split_whitespace().map(String::from)) {
+        Ok(keyword) => {
+            let mut result = pokedex.search_many(keyword);
+            result.sort();
+            json(result.pokemon())
+        }
+        Err(e) => error(400, &e),
+    }
+}
+
+fn json<T: serde::Serialize + ?Sized>(value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(body) => (200, body),
+        Err(e) => error(500, &e.to_string()),
+    }
+}
+fn error(status: u16, message: &str) -> (u16, String) {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+fn respond(request: Request, status: u16, body: String) {
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("the header is valid");
+    let mut response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(content_type);
+    if status == 405 {
+        response.add_header(Header::from_bytes("Allow", "GET").expect("the header is valid"));
+    }
+    if let Err(e) = request.respond(response) {
+        eprintln!("could not answer a request because: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiny_http::Method;
+
+    use super::answer;
+    use crate::dataset::test_pokedex;
+
+    #[test]
+    fn test_answer() {
+        let dex = test_pokedex();
+        let get = |url| answer(&dex, &Method::Get, url);
+
+        let (status, body) = get("/pokemon/25");
+        assert_eq!(status, 200);
+        assert!(body.contains("Pikachu"));
+        assert_eq!(get("/pokemon/Mr.%20Mime").0, 200);
+        let (status, body) = get("/search?q=type:fire+and+type:water");
+        assert_eq!(status, 200);
+        assert!(body.starts_with('[') && body.contains("Volcanion"));
+
+        assert_eq!(get("/search").0, 400);
+        assert_eq!(get("/search?q=typ:fire").0, 400);
+        let (status, body) = get("/pokemon/pikachoo");
+        assert_eq!(status, 404);
+        assert!(body.contains("pikachu"));
+        assert_eq!(get("/pokemon/2000").0, 404);
+        assert_eq!(get("/").0, 404);
+
+        assert_eq!(answer(&dex, &Method::Post, "/pokemon/25").0, 405);
+    }
+}
@@ -0,0 +1,391 @@
+use std::{io, path::Path};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+use rsdex_lib::{
+    Field, FieldSet,
+    color::{self, StatTier},
+    data_types::PokemonType,
+    pokedex::{Pokedex, PokedexSearchResult},
+    search::KeyWord,
+};
+
+use crate::output;
+
+///the fields `ctrl+s` goes through
+const SORT_FIELDS: [Field; 10] = [
+    Field::NationalDexNumber,
+    Field::Name,
+    Field::Bst,
+    Field::Hp,
+    Field::Attack,
+    Field::Defence,
+    Field::SpecialAttack,
+    Field::SpecialDefence,
+    Field::Speed,
+    Field::Generation,
+];
+
+///what the typed text goes into
+enum Input {
+    Filter,
+    ExportPath(String),
+}
+
+struct App<'a, P: Pokedex> {
+    pokedex: &'a P,
+    filter: String,
+    input: Input,
+    result: PokedexSearchResult,
+    list: ListState,
+    sort: usize,
+    descending: bool,
+    fields: FieldSet,
+    detail_level: u8,
+    ///the last error or what was exported
+    message: Option<String>,
+}
+
+///a full screen browser with a search box that is searched as you type
+///
+///`detail_level` is where `ctrl+d` starts counting from
+pub fn run<P: Pokedex>(pokedex: &P, fields: FieldSet, detail_level: u8) -> io::Result<()> {
+    let mut app = App::new(pokedex, fields, detail_level);
+    ratatui::run(|terminal| app.run(terminal))
+}
+
+impl<'a, P: Pokedex> App<'a, P> {
+    ///starts with every pokemon listed
+    fn new(pokedex: &'a P, fields: FieldSet, detail_level: u8) -> Self {
+        let mut app = App {
+            pokedex,
+            filter: String::new(),
+            input: Input::Filter,
+            result: PokedexSearchResult::default(),
+            list: ListState::default(),
+            sort: 0,
+            descending: false,
+            fields,
+            detail_level,
+            message: None,
+        };
+        app.search();
+        app
+    }
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle_key(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    ///an empty filter shows every pokemon, a filter that doesn't parse keeps the last results
+    fn search(&mut self) {
+        let filter = self.filter.trim();
+        let result = if filter.is_empty() {
+            Ok(PokedexSearchResult::new(
+                self.pokedex.find_many_pokemon(|_| true),
+            ))
+        } else {
+            KeyWord::parse(&mut filter.split_whitespace().map(String::from))
+                .map(|keyword| self.pokedex.search_many(keyword))
+        };
+        match result {
+            Ok(result) => {
+                self.result = result;
+                self.message = None;
+                self.sort_result();
+            }
+            Err(e) => self.message = Some(e),
+        }
+    }
+    fn sort_result(&mut self) {
+        self.result
+            .sort_by_field(SORT_FIELDS[self.sort], self.descending);
+        self.list.select((!self.result.is_empty()).then_some(0));
+    }
+
+    ///returns false to quit
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match (&mut self.input, key.code) {
+            (_, KeyCode::Char('c')) if ctrl => return false,
+            (Input::Filter, KeyCode::Esc) => return false,
+            (Input::ExportPath(_), KeyCode::Esc) => self.input = Input::Filter,
+            (Input::ExportPath(path), KeyCode::Enter) => {
+                let path = std::mem::take(path);
+                self.input = Input::Filter;
+                self.message = Some(
+                    match output::save(Path::new(&path), &self.result, &self.fields) {
+                        Ok(()) => format!("exported {} pokemon to {path}", self.result.len()),
+                        Err(e) => format!("could not export: {e}"),
+                    },
+                );
+            }
+            (Input::ExportPath(path), KeyCode::Backspace) => {
+                path.pop();
+            }
+            (Input::ExportPath(path), KeyCode::Char(c)) if !ctrl => path.push(c),
+            (_, KeyCode::Char('s')) if ctrl => {
+                self.sort = (self.sort + 1) % SORT_FIELDS.len();
+                self.sort_result();
+            }
+            (_, KeyCode::Char('r')) if ctrl => {
+                self.descending = !self.descending;
+                self.sort_result();
+            }
+            (_, KeyCode::Char('d')) if ctrl => {
+                self.detail_level = (self.detail_level + 1) % 5;
+                self.fields = FieldSet::from_detail_level(self.detail_level);
+            }
+            (_, KeyCode::Char('e')) if ctrl => {
+                self.input = Input::ExportPath(String::from("rsdex.csv"));
+            }
+            (Input::Filter, KeyCode::Char('u')) if ctrl => {
+                self.filter.clear();
+                self.search();
+            }
+            (Input::Filter, KeyCode::Char(c)) if !ctrl => {
+                self.filter.push(c);
+                self.search();
+            }
+            (Input::Filter, KeyCode::Backspace) => {
+                self.filter.pop();
+                self.search();
+            }
+            (_, KeyCode::Down) => self.list.select_next(),
+            (_, KeyCode::Up) => self.list.select_previous(),
+            (_, KeyCode::PageDown) => self.list.scroll_down_by(10),
+            (_, KeyCode::PageUp) => self.list.scroll_up_by(10),
+            (_, KeyCode::Home) => self.list.select_first(),
+            (_, KeyCode::End) => self.list.select_last(),
+            _ => (),
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [input, main, status] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list, detail] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(main);
+
+        let (title, text) = match &self.input {
+            Input::Filter => (" search ", &self.filter),
+            Input::ExportPath(path) => (" export to ", path),
+        };
+        frame.render_widget(
+            Paragraph::new(text.as_str()).block(Block::bordered().title(title)),
+            input,
+        );
+        frame.set_cursor_position((input.x + 1 + text.chars().count() as u16, input.y + 1));
+
+        self.draw_list(frame, list);
+        if let Some(pokemon) = self
+            .list
+            .selected()
+            .and_then(|selected| self.result.pokemon().get(selected))
+        {
+            frame.render_widget(
+                Paragraph::new(detail_lines(pokemon, &self.fields, detail.width))
+                    .block(Block::bordered().title(format!(" {} ", pokemon.get_name()))),
+                detail,
+            );
+        } else {
+            frame.render_widget(Block::bordered(), detail);
+        }
+
+        let status_text = match &self.message {
+            Some(message) => Line::from(message.as_str()).yellow(),
+            None => Line::from(format!(
+                "sorted by {}{}  ctrl+s sort  ctrl+r reverse  ctrl+d detail level {}  ctrl+e export  esc quit",
+                SORT_FIELDS[self.sort].label(),
+                if self.descending { " ↓" } else { " ↑" },
+                self.detail_level,
+            ))
+            .dark_gray(),
+        };
+        frame.render_widget(status_text, status);
+    }
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let sort = SORT_FIELDS[self.sort];
+        let items: Vec<ListItem> = self
+            .result
+            .pokemon()
+            .iter()
+            .map(|pokemon| {
+                let mut spans = vec![
+                    Span::raw(format!("{:>4} ", pokemon.get_dex_number())).dark_gray(),
+                    Span::raw(pokemon.get_name().to_string()),
+                ];
+                //shows what the list is sorted by
+                if !matches!(sort, Field::NationalDexNumber | Field::Name) {
+                    spans.push(Span::raw(format!(" {}", pokemon.get_field(sort))).dark_gray());
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {} pokemon ", self.result.len())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+}
+
+///the fields that aren't stats as `label: value` and the stats as bars
+fn detail_lines<'a>(pokemon: &rsdex_lib::Pokemon, fields: &FieldSet, width: u16) -> Vec<Line<'a>> {
+    let mut lines: Vec<Line> = fields
+        .without_stats()
+        .fields()
+        .iter()
+        .filter(|field| **field != Field::Name)
+        .map(|field| {
+            let value = Span::raw(pokemon.get_field(*field));
+            let value = match field {
+                Field::Type1 => value.fg(type_color(pokemon.get_primary_type())),
+                Field::Type2 => value.fg(type_color(pokemon.get_seconary_type())),
+                _ => value,
+            };
+            Line::from(vec![
+                Span::raw(format!("{}: ", field.label())).bold(),
+                value,
+            ])
+        })
+        .collect();
+    lines.push(Line::default());
+    //borders, the label and the number
+    let bar_width = width.saturating_sub(2 + 18 + 4) as usize;
+    for (field, stat) in Field::STATS.iter().zip(pokemon.get_stats()) {
+        let length = stat as usize * bar_width / 255;
+        lines.push(Line::from(vec![
+            Span::raw(format!("{:<18}{stat:>3} ", field.label())),
+            Span::raw("█".repeat(length)).fg(stat_color(stat)),
+        ]));
+    }
+    lines.push(Line::from(format!(
+        "{:<18}{:>3}",
+        Field::Bst.label(),
+        pokemon.base_stat_total()
+    )));
+    lines
+}
+
+fn type_color(ptype: &PokemonType) -> Color {
+    let (r, g, b) = color::type_color(ptype);
+    Color::Rgb(r, g, b)
+}
+fn stat_color(stat: u8) -> Color {
+    match StatTier::of(stat) {
+        StatTier::Bad => Color::Red,
+        StatTier::Low => Color::Yellow,
+        StatTier::Average => Color::LightYellow,
+        StatTier::Good => Color::Green,
+        StatTier::Great => Color::Cyan,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use rsdex_lib::{FieldSet, data_types::PokemonType};
+
+    use super::{App, SORT_FIELDS};
+    use crate::dataset::test_pokedex;
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+    fn names<P: rsdex_lib::pokedex::Pokedex>(app: &App<P>) -> Vec<String> {
+        app.result
+            .pokemon()
+            .iter()
+            .map(|pokemon| pokemon.get_name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_sorting_keys() {
+        let dex = test_pokedex();
+        let mut app = App::new(&dex, FieldSet::from_detail_level(0), 0);
+        assert_eq!(app.result.len(), 1025);
+        assert_eq!(names(&app)[0], "Bulbasaur");
+
+        assert!(app.handle_key(ctrl('s')));
+        let mut sorted = names(&app);
+        sorted.sort_by_key(|name| name.to_lowercase());
+        assert_eq!(names(&app), sorted);
+        assert_eq!(app.list.selected(), Some(0));
+
+        assert!(app.handle_key(ctrl('r')));
+        sorted.reverse();
+        assert_eq!(names(&app), sorted);
+
+        //goes back to the dex number after the last field
+        for _ in 1..SORT_FIELDS.len() {
+            app.handle_key(ctrl('s'));
+        }
+        assert_eq!(app.sort, 0);
+        assert_eq!(names(&app)[0], "Pecharunt");
+    }
+
+    #[test]
+    fn test_detail_level_key() {
+        let dex = test_pokedex();
+        let mut app = App::new(&dex, FieldSet::from_detail_level(3), 3);
+        app.handle_key(ctrl('d'));
+        assert_eq!(app.detail_level, 4);
+        assert_eq!(app.fields, FieldSet::from_detail_level(4));
+        app.handle_key(ctrl('d'));
+        assert_eq!(app.detail_level, 0);
+        assert_eq!(app.fields, FieldSet::from_detail_level(0));
+    }
+
+    #[test]
+    fn test_filter_keeps_the_last_results() {
+        let dex = test_pokedex();
+        let mut app = App::new(&dex, FieldSet::from_detail_level(0), 0);
+        for c in "type:fire".chars() {
+            assert!(app.handle_key(KeyEvent::from(KeyCode::Char(c))));
+        }
+        let fire = names(&app);
+        assert!(app.message.is_none());
+        assert!(app.result.pokemon().iter().all(|pokemon| {
+            [pokemon.get_primary_type(), pokemon.get_seconary_type()].contains(&&PokemonType::Fire)
+        }));
+
+        //`type:fire a` doesn't parse so the fire types stay
+        for c in " a".chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!(app.message.is_some());
+        assert_eq!(names(&app), fire);
+
+        app.handle_key(KeyEvent::from(KeyCode::Backspace));
+        app.handle_key(KeyEvent::from(KeyCode::Backspace));
+        assert_eq!(app.filter, "type:fire");
+        assert!(app.message.is_none());
+
+        app.handle_key(ctrl('u'));
+        assert!(app.filter.is_empty());
+        assert_eq!(app.result.len(), 1025);
+
+        assert!(!app.handle_key(KeyEvent::from(KeyCode::Esc)));
+        assert!(!app.handle_key(ctrl('c')));
+    }
+}
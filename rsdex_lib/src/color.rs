@@ -0,0 +1,152 @@
+use strum::{Display, EnumString, VariantNames};
+
+use crate::{Pokemon, data_types::PokemonType, fields::Field};
+
+const RESET: &str = "\x1b[0m";
+
+///when output should be colored
+#[derive(Clone, Copy, Debug, PartialEq, Default, Display, EnumString, VariantNames)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ColorChoice {
+    ///only if writing to a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+impl ColorChoice {
+    pub fn enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal
+                    && std::env::var_os("NO_COLOR").is_none_or(|no_color| no_color.is_empty())
+            }
+        }
+    }
+}
+
+///adds ansi colors to text, does nothing when disabled
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Painter {
+    enabled: bool,
+}
+impl Painter {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+    ///a painter that never colors
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+    pub fn bold(&self, text: &str) -> String {
+        self.wrap("1", text)
+    }
+    ///the type name in front of its color
+    pub fn type_badge(&self, ptype: &PokemonType) -> String {
+        if !self.enabled {
+            return ptype.to_string();
+        }
+        let (r, g, b) = type_color(ptype);
+        self.wrap(
+            &format!("1;38;2;255;255;255;48;2;{r};{g};{b}"),
+            &format!(" {ptype} "),
+        )
+    }
+    ///`text` in the color of the type
+    pub fn type_text(&self, ptype: &PokemonType, text: &str) -> String {
+        let (r, g, b) = type_color(ptype);
+        self.wrap(&format!("38;2;{r};{g};{b}"), text)
+    }
+    ///`text` colored by how good `stat` is
+    pub fn stat(&self, stat: u8, text: &str) -> String {
+        self.wrap(StatTier::of(stat).ansi_code(), text)
+    }
+    ///`text` colored by how good the base stat total is
+    pub fn base_stat_total(&self, bst: u16, text: &str) -> String {
+        //same tiers as a single stat using the average
+        self.stat((bst / 6).min(u8::MAX as u16) as u8, text)
+    }
+    ///colors `text` the way that suits the field, used for tables
+    pub fn field(&self, pokemon: &Pokemon, field: Field, text: &str) -> String {
+        let stats = pokemon.get_stats();
+        match field {
+            Field::Type1 => self.type_text(pokemon.get_primary_type(), text),
+            Field::Type2 => self.type_text(pokemon.get_seconary_type(), text),
+            Field::Hp => self.stat(stats[0], text),
+            Field::Attack => self.stat(stats[1], text),
+            Field::Defence => self.stat(stats[2], text),
+            Field::SpecialAttack => self.stat(stats[3], text),
+            Field::SpecialDefence => self.stat(stats[4], text),
+            Field::Speed => self.stat(stats[5], text),
+            Field::Bst => self.base_stat_total(pokemon.base_stat_total(), text),
+            _ => text.to_string(),
+        }
+    }
+    fn wrap(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{code}m{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+///the colors the games use for each type
+pub fn type_color(ptype: &PokemonType) -> (u8, u8, u8) {
+    match ptype {
+        PokemonType::Normal => (168, 167, 122),
+        PokemonType::Fire => (238, 129, 48),
+        PokemonType::Water => (99, 144, 240),
+        PokemonType::Grass => (122, 199, 76),
+        PokemonType::Flying => (169, 143, 243),
+        PokemonType::Fighting => (194, 46, 40),
+        PokemonType::Poison => (163, 62, 161),
+        PokemonType::Electric => (247, 208, 44),
+        PokemonType::Ground => (226, 191, 101),
+        PokemonType::Rock => (182, 161, 54),
+        PokemonType::Psychic => (249, 85, 135),
+        PokemonType::Ice => (150, 217, 214),
+        PokemonType::Bug => (166, 185, 26),
+        PokemonType::Ghost => (115, 87, 151),
+        PokemonType::Steel => (183, 183, 206),
+        PokemonType::Dragon => (111, 53, 252),
+        PokemonType::Dark => (112, 87, 70),
+        PokemonType::Fairy => (214, 133, 173),
+        PokemonType::None => (128, 128, 128),
+    }
+}
+
+///how good a base stat is, anything showing stats colors them by it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatTier {
+    ///below 50
+    Bad,
+    Low,
+    Average,
+    Good,
+    ///120 and up
+    Great,
+}
+impl StatTier {
+    pub fn of(stat: u8) -> Self {
+        match stat {
+            0..50 => StatTier::Bad,
+            50..80 => StatTier::Low,
+            80..100 => StatTier::Average,
+            100..120 => StatTier::Good,
+            _ => StatTier::Great,
+        }
+    }
+    ///red for bad stats up to cyan for great ones
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            StatTier::Bad => "31",
+            StatTier::Low => "33",
+            StatTier::Average => "93",
+            StatTier::Good => "32",
+            StatTier::Great => "36",
+        }
+    }
+}
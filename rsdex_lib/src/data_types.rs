@@ -16,6 +16,14 @@ macro_rules! string_new_type {
             pub fn new(s: &str) -> Self {
                 Self::from(StringId::new(s))
             }
+            ///the reverse of [`Display`], unlike [`new`](Self::new) it keeps the capital letters
+            pub fn from_display(s: &str) -> Self {
+                Self::from(StringId::from_value(s))
+            }
+            ///how the data set writes it, `arena-trap` for `ArenaTrap`, this is also what searches expect
+            pub fn to_kebab_case(&self) -> String {
+                self.0.kebab_value()
+            }
         }
         impl FromStr for $name {
             type Err = ();
@@ -237,7 +245,7 @@ impl<'d> Nullable<'d> for EggGroup {
     }
 }
 #[cfg_attr(feature = "file_writing", derive(serde::Serialize))]
-#[derive(Deserialize, EnumString, Clone, PartialEq, Hash, Debug, Display)]
+#[derive(Deserialize, EnumString, Clone, PartialEq, Hash, Debug, Display, VariantNames)]
 #[strum(ascii_case_insensitive)]
 #[serde(rename_all = "kebab-case")]
 pub enum BodyShape {
@@ -271,7 +279,16 @@ impl NationalPokedexNumber {
     pub fn number(&self) -> u16 {
         self.0
     }
+    ///the generation the pokemon was introduced in
+    pub fn generation(&self) -> u8 {
+        GENERATION_ENDS
+            .iter()
+            .position(|last| self.0 <= *last)
+            .map_or(GENERATION_ENDS.len() as u8 + 1, |index| index as u8 + 1)
+    }
 }
+///the last national dex number of each generation
+pub const GENERATION_ENDS: [u16; 9] = [151, 251, 386, 493, 649, 721, 809, 905, 1025];
 #[derive(Debug)]
 pub struct InvalidDexNum;
 
@@ -0,0 +1,227 @@
+use std::{fmt::Display, str::FromStr};
+
+use strum::{Display, EnumString, VariantNames};
+
+use crate::compute_similarity;
+
+///a single column of a [`Pokemon`](crate::Pokemon)
+///
+///parses from the same names the fields have in the data set so `type1` or `special_attack`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, EnumString, Display, VariantNames)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum Field {
+    Name,
+    #[strum(
+        to_string = "national_dex_number",
+        serialize = "dex",
+        serialize = "num"
+    )]
+    NationalDexNumber,
+    Genus,
+    Type1,
+    Type2,
+    Color,
+    EggGroup1,
+    EggGroup2,
+    Ability1,
+    Ability2,
+    HiddenAbility,
+    Shape,
+    Hp,
+    Attack,
+    Defence,
+    SpecialAttack,
+    SpecialDefence,
+    Speed,
+    ///base stat total
+    Bst,
+    ///worked out from the dex number
+    #[strum(serialize = "generation", serialize = "gen")]
+    Generation,
+}
+impl Field {
+    ///the human readable name used as a key or column header
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::NationalDexNumber => "national dex number",
+            Field::Genus => "genus",
+            Field::Type1 => "primary type",
+            Field::Type2 => "secondary type",
+            Field::Color => "color",
+            Field::EggGroup1 => "egg group 1",
+            Field::EggGroup2 => "egg group 2",
+            Field::Ability1 => "ability 1",
+            Field::Ability2 => "ability 2",
+            Field::HiddenAbility => "hidden ability",
+            Field::Shape => "shape",
+            Field::Hp => "hp",
+            Field::Attack => "attack",
+            Field::Defence => "defence",
+            Field::SpecialAttack => "special attack",
+            Field::SpecialDefence => "special defence",
+            Field::Speed => "speed",
+            Field::Bst => "base stat total",
+            Field::Generation => "generation",
+        }
+    }
+    ///a field from its [`label`](Self::label) or its name
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim();
+        FieldSet::all()
+            .fields()
+            .iter()
+            .find(|field| field.label().eq_ignore_ascii_case(label))
+            .copied()
+            .or_else(|| Field::from_str(label).ok())
+    }
+    ///if the values are numbers that should be right aligned
+    pub fn is_numeric(&self) -> bool {
+        self.is_stat()
+            || matches!(
+                self,
+                Field::NationalDexNumber | Field::Bst | Field::Generation
+            )
+    }
+    ///the six base stats in the order [`Pokemon::get_stats`](crate::Pokemon::get_stats) gives them
+    pub const STATS: [Field; 6] = [
+        Field::Hp,
+        Field::Attack,
+        Field::Defence,
+        Field::SpecialAttack,
+        Field::SpecialDefence,
+        Field::Speed,
+    ];
+    ///one of the six base stats, not the total
+    pub fn is_stat(&self) -> bool {
+        matches!(
+            self,
+            Field::Hp
+                | Field::Attack
+                | Field::Defence
+                | Field::SpecialAttack
+                | Field::SpecialDefence
+                | Field::Speed
+        )
+    }
+}
+
+use Field::*;
+///the fields each of the old detail levels adds
+#[rustfmt::skip]
+const DETAIL_LEVELS: [&[Field]; 5] = [
+    &[Name, NationalDexNumber],
+    &[Genus, Type1, Type2],
+    &[Color, EggGroup1, EggGroup2],
+    &[Ability1, Ability2, HiddenAbility, Shape],
+    &[Hp, Attack, Defence, SpecialAttack, SpecialDefence, Speed],
+];
+///named versions of the detail levels
+const PRESETS: [(&str, u8); 5] = [
+    ("basic", 0),
+    ("types", 1),
+    ("breeding", 2),
+    ("abilities", 3),
+    ("stats", 4),
+];
+
+///an ordered selection of [`Field`]s that decides what gets shown or written
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldSet(Vec<Field>);
+impl FieldSet {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self(fields)
+    }
+    ///the fields of a detail level, anything above 4 is treated as 4
+    pub fn from_detail_level(detail_level: u8) -> Self {
+        Self(
+            DETAIL_LEVELS
+                .iter()
+                .take(detail_level as usize + 1)
+                .flat_map(|level| level.iter().copied())
+                .collect(),
+        )
+    }
+    ///every field there is
+    pub fn all() -> Self {
+        Self::new(
+            Field::VARIANTS
+                .iter()
+                .map(|name| Field::from_str(name).expect("variant names parse"))
+                .collect(),
+        )
+    }
+    pub fn fields(&self) -> &[Field] {
+        &self.0
+    }
+    pub fn contains(&self, field: Field) -> bool {
+        self.0.contains(&field)
+    }
+    ///the same fields with the stats and their total left out
+    pub fn without_stats(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|field| !field.is_stat() && **field != Field::Bst)
+                .copied()
+                .collect(),
+        )
+    }
+    pub fn preset_names() -> Vec<&'static str> {
+        PRESETS
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(["all"])
+            .collect()
+    }
+}
+impl From<u8> for FieldSet {
+    fn from(detail_level: u8) -> Self {
+        Self::from_detail_level(detail_level)
+    }
+}
+impl FromStr for FieldSet {
+    type Err = String;
+    ///either the name of a preset or a comma separated list of fields
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Self::all());
+        }
+        if let Some((_, level)) = PRESETS
+            .iter()
+            .find(|(name, _)| s.eq_ignore_ascii_case(name))
+        {
+            return Ok(Self::from_detail_level(*level));
+        }
+        let mut fields = Vec::new();
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            match Field::from_str(name) {
+                Ok(field) => fields.push(field),
+                Err(_) => {
+                    let mut options = Field::VARIANTS.to_vec();
+                    options.extend(Self::preset_names());
+                    let suggestions = compute_similarity(name, &options);
+                    return Err(if suggestions.is_empty() {
+                        format!("unknown field '{name}'")
+                    } else {
+                        format!(
+                            "unknown field '{name}' did you mean: {}",
+                            suggestions.join(",")
+                        )
+                    });
+                }
+            }
+        }
+        if fields.is_empty() {
+            return Err("no fields were given".into());
+        }
+        Ok(Self(fields))
+    }
+}
+impl Display for FieldSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self.0.iter().map(Field::to_string).collect();
+        write!(f, "{}", names.join(","))
+    }
+}
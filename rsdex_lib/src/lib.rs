@@ -2,17 +2,29 @@ use std::range::Range;
 
 use strsim::damerau_levenshtein;
 
+pub mod color;
 pub mod data_types;
+pub mod fields;
 pub mod pokedex;
 pub mod pokemon;
+pub mod reading;
 pub mod search;
 mod string_id;
+pub mod summary;
+pub mod table;
+pub mod template;
+pub mod vocabulary;
 #[cfg(feature = "file_writing")]
 pub mod writing;
 
 #[cfg(feature = "file_writing")]
-pub use writing::WriteType;
-pub use {pokedex::max_pokedex_number, pokemon::Pokemon};
+pub use writing::{PokemonWriter, WriteType};
+pub use {
+    fields::{Field, FieldSet},
+    pokedex::max_pokedex_number,
+    pokemon::Pokemon,
+    template::Template,
+};
 
 fn compute_similarity(string: &str, options: &[&str]) -> Vec<String> {
     options
@@ -32,8 +44,8 @@ fn str_to_range(input: &str) -> Result<Range<u16>, RangeParseError> {
         return Err(RangeParseError);
     }
     let (min, max) = input.split_at(input.find("..").unwrap());
-    let min = min.parse::<u16>().unwrap();
-    let max = max[2..].parse().unwrap();
+    let min = min.parse::<u16>().map_err(|_| RangeParseError)?;
+    let max = max[2..].parse().map_err(|_| RangeParseError)?;
     if min >= max || max > max_pokedex_number() || min < 1 {
         return Err(RangeParseError);
     }
@@ -48,10 +60,10 @@ mod pokedex_tests {
     // use crate::{pokedex::Pokedex, pokemon::Pokemon};
 
     impl PokeDexMmap {
-        fn get(&self, name: &str) -> Pokemon {
+        pub(crate) fn get(&self, name: &str) -> Pokemon {
             self.find_by_name(&name.try_into().unwrap()).unwrap()
         }
-        fn id(&self, id: u16) -> Pokemon {
+        pub(crate) fn id(&self, id: u16) -> Pokemon {
             self.find_by_natinal_dex_number(&id.try_into().unwrap())
                 .unwrap()
         }
@@ -156,11 +168,229 @@ mod pokedex_tests {
         );
         Ok(())
     }
+    #[test]
+    fn test_sort_by_field() {
+        let dex = PokeDexMmap::new().unwrap();
+        let mut result = dex.search_many(KeyWord::Query(SearchQuery::Range(
+            std::range::Range::from(0..5),
+        )));
+        let order = |result: &PokedexSearchResult| {
+            result
+                .pokemon()
+                .iter()
+                .map(|pokemon| pokemon.get_dex_number().number())
+                .collect::<Vec<_>>()
+        };
+        result.sort_by_field(crate::Field::Speed, true);
+        assert_eq!(order(&result), [3, 4, 2, 1]);
+        result.sort_by_field(crate::Field::Type2, false);
+        //charmander has no secondary type, the rest are all poison and stay in dex order
+        assert_eq!(order(&result), [4, 1, 2, 3]);
+    }
+    #[test]
+    fn test_resolve() {
+        let dex = PokeDexMmap::new().unwrap();
+        let name = |input| {
+            dex.resolve(input)
+                .map(|pokemon| pokemon.get_name().to_string())
+        };
+        assert_eq!(name("25"), Ok("Pikachu".to_string()));
+        assert_eq!(name(" #6 "), Ok("Charizard".to_string()));
+        for input in ["Mr. Mime", "mr-mime", "MrMime"] {
+            assert_eq!(name(input), Ok("MrMime".to_string()));
+        }
+        assert!(name("pikachuu").is_err_and(|e| e.contains("did you mean: pikachu")));
+        assert!(name("9999").is_err());
+        assert!(name("not a pokemon at all").is_err_and(|e| !e.contains("did you mean")));
+    }
+}
+
+#[cfg(test)]
+mod vocabulary_tests {
+    use crate::{
+        data_types::BodyShape,
+        pokedex::{PokeDexMmap, Pokedex},
+        search::KeyWord,
+        vocabulary::Vocabulary,
+    };
+    use strum::VariantNames;
+
+    #[test]
+    fn test_vocabulary_values_search() {
+        let dex = PokeDexMmap::new().unwrap();
+        for vocabulary in [Vocabulary::Types, Vocabulary::Colors, Vocabulary::EggGroups] {
+            let key = vocabulary.search_key().unwrap();
+            for (value, amount) in vocabulary.values(&dex) {
+                let result = dex.search_many(KeyWord::query(key, &value).unwrap());
+                assert_eq!(result.len(), amount, "{vocabulary} {value}");
+            }
+        }
+        let abilities = Vocabulary::Abilities.values(&dex);
+        assert!(abilities.iter().any(|(value, _)| value == "arena-trap"));
+        assert!(abilities.iter().all(|(value, _)| value != "none"));
+    }
+    #[test]
+    fn test_vocabulary_values_list() {
+        let dex = PokeDexMmap::new().unwrap();
+        //enums keep their order and keep values no pokemon has
+        let shapes = Vocabulary::Shapes.values(&dex);
+        assert_eq!(shapes.len(), BodyShape::VARIANTS.len());
+        assert_eq!(shapes.iter().map(|(_, amount)| amount).sum::<usize>(), 1025);
+        let names = Vocabulary::Names.values(&dex);
+        assert!(names.is_sorted());
+        assert!(names.contains(&("mr-mime".to_string(), 1)));
+        assert_eq!(names.len(), 1025);
+        assert_eq!("egg-groups".parse(), Ok(Vocabulary::EggGroups));
+        assert!("moves".parse::<Vocabulary>().is_err());
+    }
 }
 
+#[cfg(test)]
+mod summary_tests {
+    use crate::{
+        pokedex::{PokeDexMmap, VecPokedex},
+        summary::Summary,
+    };
+
+    #[test]
+    fn test_summary() {
+        let dex = PokeDexMmap::new().unwrap();
+        let summary = Summary::new(&dex);
+        assert_eq!(summary.count, 1025);
+        assert_eq!(summary.dex_numbers, Some((1, 1025)));
+        assert!(summary.gaps.is_empty());
+        assert_eq!(summary.generations[0], (1, 151));
+        assert_eq!(summary.extremes[0].pokemon, ["Regieleki"]);
+
+        let custom = VecPokedex::new(vec![dex.id(1), dex.id(4), dex.id(4), dex.id(7), dex.id(9)]);
+        let summary = Summary::new(&custom);
+        assert_eq!(summary.dex_numbers, Some((1, 9)));
+        assert_eq!(summary.gaps, [(2, 3), (5, 6), (8, 8)]);
+        assert_eq!(summary.duplicates, [4]);
+    }
+}
+
+#[cfg(test)]
+mod table_tests {
+    use crate::{
+        FieldSet,
+        color::{ColorChoice, Painter, StatTier},
+        data_types::PokemonType,
+        pokedex::{PokeDexMmap, Pokedex},
+        search::SearchQuery,
+        table::render_table,
+    };
+
+    #[test]
+    fn test_table_alignment() {
+        let dex = PokeDexMmap::new().unwrap();
+        let result = dex
+            .search(&SearchQuery::Range(std::range::Range::from(0..3)))
+            .to_vec();
+        let fields = "name,type2,speed".parse::<FieldSet>().unwrap();
+        assert_eq!(
+            render_table(&result, &fields, None, &Painter::plain()),
+            "name       secondary type  speed\n\
+             ─────────  ──────────────  ─────\n\
+             Bulbasaur  Poison             45\n\
+             Ivysaur    Poison             60\n"
+        );
+    }
+    #[test]
+    fn test_table_truncates_to_width() {
+        let dex = PokeDexMmap::new().unwrap();
+        let result = dex.search(&SearchQuery::nat_dex(1)).to_vec();
+        let fields = "name,genus".parse::<FieldSet>().unwrap();
+        let table = render_table(&result, &fields, Some(16), &Painter::plain());
+        assert!(table.lines().all(|line| line.chars().count() <= 16));
+        assert!(table.contains('…'));
+    }
+    #[test]
+    fn test_stat_bars() {
+        let dex = PokeDexMmap::new().unwrap();
+        let blissey = dex.id(242);
+        let bars = blissey.get_stat_bars(&Painter::plain());
+        let lines: Vec<&str> = bars.lines().collect();
+        assert_eq!(lines[0], format!("hp              255 {}", "█".repeat(32)));
+        assert_eq!(lines[1], "attack           10 █▎");
+        assert_eq!(lines[6], "base stat total: 540");
+    }
+    #[test]
+    fn test_colored_table_matches_plain() {
+        let dex = PokeDexMmap::new().unwrap();
+        let result = dex.search(&SearchQuery::Type(PokemonType::Dragon)).to_vec();
+        let fields = "name,type1,type2,speed,bst".parse::<FieldSet>().unwrap();
+        let colored = render_table(&result, &fields, None, &Painter::new(true));
+        let mut stripped = String::new();
+        let mut in_escape = false;
+        for c in colored.chars() {
+            match c {
+                '\x1b' => in_escape = true,
+                'm' if in_escape => in_escape = false,
+                _ if in_escape => (),
+                c => stripped.push(c),
+            }
+        }
+        assert_ne!(colored, stripped);
+        assert_eq!(
+            stripped,
+            render_table(&result, &fields, None, &Painter::plain())
+        );
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(ColorChoice::Always.enabled(false));
+    }
+    #[test]
+    fn test_stat_tiers() {
+        assert_eq!(StatTier::of(49), StatTier::Bad);
+        assert_eq!(StatTier::of(50), StatTier::Low);
+        assert_eq!(StatTier::of(80), StatTier::Average);
+        assert_eq!(StatTier::of(119), StatTier::Good);
+        assert_eq!(StatTier::of(255), StatTier::Great);
+    }
+}
+
+#[cfg(test)]
+mod template_tests {
+    use crate::{
+        Template,
+        pokedex::{PokeDexMmap, Pokedex},
+        search::SearchQuery,
+    };
+
+    #[test]
+    fn test_template_render() {
+        let dex = PokeDexMmap::new().unwrap();
+        let template: Template =
+            "{name} (#{num}) {type1}{?type2}/{type2}{/}{!type2} only{/} spe={speed:>4}|{{x}}"
+                .parse()
+                .unwrap();
+        let charizard = dex.search(&SearchQuery::nat_dex(6)).to_vec();
+        assert_eq!(
+            template.render(&charizard[0]),
+            "Charizard (#6) Fire/Flying spe= 100|{x}"
+        );
+        let charmander = dex.search(&SearchQuery::nat_dex(4)).to_vec();
+        assert_eq!(
+            template.render(&charmander[0]),
+            "Charmander (#4) Fire only spe=  65|{x}"
+        );
+        let padded: Template = "[{name:^9}][{num:<3}][{hp:5}]".parse().unwrap();
+        assert_eq!(padded.render(&charmander[0]), "[Charmander][4  ][   39]");
+    }
+    #[test]
+    fn test_template_errors() {
+        assert!("{nam}".parse::<Template>().unwrap_err().contains("name"));
+        assert!("{?type2}".parse::<Template>().is_err());
+        assert!("{/}".parse::<Template>().is_err());
+        assert!("{name".parse::<Template>().is_err());
+        assert!("}".parse::<Template>().is_err());
+        assert!("{speed:>x}".parse::<Template>().is_err());
+    }
+}
 #[cfg(test)]
 mod parsing {
     use crate::{
+        Field, FieldSet,
         data_types::{PokemonName, PokemonType},
         pokedex_tests::TestResult,
         search::{KeyWord, SearchQuery, SearchQueryParsing},
@@ -168,7 +398,8 @@ mod parsing {
 
     impl SearchQuery {
         fn parses_to(what_type: SearchQueryParsing, input: &str, other: Self) -> TestResult {
-            Ok(assert_eq!(Self::parse(what_type, input)?, other))
+            assert_eq!(Self::parse(what_type, input)?, other);
+            Ok(())
         }
     }
 
@@ -189,6 +420,18 @@ mod parsing {
         Ok(())
     }
     #[test]
+    fn test_keyword_parse_errors() {
+        let parse =
+            |tokens: &[&str]| KeyWord::parse(&mut tokens.iter().map(|token| token.to_string()));
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["typ:fire"]).is_err_and(|e| e.contains("did you mean: type")));
+        assert!(parse(&["type:fire", "and"]).is_err());
+        assert!(parse(&["range:a..b"]).is_err());
+        //half typed in the tui
+        assert!(parse(&["type:"]).is_err());
+        assert!(parse(&["name:mr-"]).is_ok());
+    }
+    #[test]
     fn test_and_parse() -> TestResult {
         let test = KeyWord::and(
             KeyWord::query(SearchQueryParsing::NatDex, "1")?,
@@ -251,6 +494,16 @@ mod parsing {
         SearchQuery::parses_to(SearchQueryParsing::Range, "1..4", SearchQuery::Range(std::range::Range::from(0..5)))
     }
     #[test]
+    fn test_field_set_parse() -> TestResult {
+        assert_eq!(
+            "name, type1,speed,BST".parse::<FieldSet>()?,
+            FieldSet::new(vec![Field::Name, Field::Type1, Field::Speed, Field::Bst])
+        );
+        assert_eq!("types".parse::<FieldSet>()?, FieldSet::from_detail_level(1));
+        assert!("name,sped".parse::<FieldSet>().is_err());
+        Ok(())
+    }
+    #[test]
     fn test_type_parse() -> TestResult {
         SearchQuery::parses_to(
             SearchQueryParsing::Type,
@@ -262,17 +515,21 @@ mod parsing {
 #[cfg(all(feature = "file_writing", test))]
 mod writing_tests {
 
+    use std::io::{self, Write};
+
     use crate::{
-        WriteType,
+        Field, FieldSet, Pokemon, PokemonWriter, WriteType,
         data_types::NationalPokedexNumber,
-        pokedex::{PokeDexMmap, Pokedex, PokedexSearchResult},
-        search::SearchQuery,
+        pokedex::{PokeDexMmap, Pokedex, PokedexSearchResult, VecPokedex},
+        reading::{ReadType, read_pokemon},
+        search::{KeyWord, SearchQuery, SearchQueryParsing},
+        writing::{BatchWriter, SplitWriter, escape_html, escape_markdown},
     };
     impl PokedexSearchResult {
         #[cfg(test)]
         fn test_write(&self) -> String {
             let mut writer = Vec::new();
-            self.write_data(&mut writer, 5, WriteType::Txt, false)
+            self.write_data(&mut writer, WriteType::Txt.writer(5.into(), false).as_mut())
                 .unwrap();
             String::from_utf8(writer).unwrap()
         }
@@ -288,4 +545,350 @@ mod writing_tests {
             "name:Diglett\nnational dex number:50\ngenus:Mole pokémon\nprimary type:Ground\nsecondary type:None\ncolor:Brown\negg group 1:Field\negg group 2:None\nability 1:SandVeil\nability 2:ArenaTrap\nhidden ability:SandForce\nshape:Blob\nhp:10\nattack:55\ndefence:25\nspecial attack:35\nspecial defence:45\nspeed:95\n\n"
         )
     }
+    struct NameWriter;
+    impl PokemonWriter for NameWriter {
+        fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+            writer.write_all(b"<")
+        }
+        fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+            write!(writer, "{};", pokemon.get_name())
+        }
+        fn end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+            writer.write_all(b">")
+        }
+    }
+    #[test]
+    fn test_csv_field_order() {
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        let fields = "speed,name,bst".parse().unwrap();
+        s.search(&SearchQuery::nat_dex(25))
+            .write_data(&mut writer, WriteType::Csv.writer(fields, false).as_mut())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "speed,name,base stat total\n90,Pikachu,320\n"
+        )
+    }
+    #[test]
+    fn test_append_writers() {
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        for (num, append) in [(25, false), (26, true)] {
+            let mut csv = if append {
+                WriteType::Csv.append_writer(0.into(), false, true).unwrap()
+            } else {
+                WriteType::Csv.writer(0.into(), false)
+            };
+            s.search(&SearchQuery::nat_dex(num))
+                .write_data(&mut writer, csv.as_mut())
+                .unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "name,national dex number\nPikachu,25\nRaichu,26\n"
+        );
+
+        //rsdex doesn't end jsonl with a new line but jq and most other tools do
+        for (existing, ends_with_new_line) in [("{}", false), ("{}\n", true)] {
+            let mut writer = existing.as_bytes().to_vec();
+            s.search(&SearchQuery::nat_dex(26))
+                .write_data(
+                    &mut writer,
+                    WriteType::Jsonl
+                        .append_writer(0.into(), false, ends_with_new_line)
+                        .unwrap()
+                        .as_mut(),
+                )
+                .unwrap();
+            assert_eq!(
+                String::from_utf8(writer).unwrap(),
+                "{}\n{\"name\":\"Raichu\",\"national dex number\":\"26\"}"
+            );
+        }
+        assert!(
+            WriteType::Json
+                .append_writer(0.into(), false, true)
+                .is_none()
+        );
+    }
+    #[test]
+    fn test_markdown_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        let fields = "name,type2,speed".parse().unwrap();
+        s.search(&SearchQuery::Range(std::range::Range::from(772..774)))
+            .write_data(
+                &mut writer,
+                WriteType::Markdown.writer(fields, false).as_mut(),
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "| name | secondary type | speed |\n\
+             | --- | --- | ---: |\n\
+             | TypeNull | None | 59 |\n\
+             | Silvally | None | 95 |\n"
+        );
+        assert_eq!(escape_markdown("a|b_c\nd"), "a\\|b\\_c<br>d");
+        assert!(matches!("md".parse(), Ok(WriteType::Markdown)));
+    }
+    #[test]
+    fn test_batch_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let fields: FieldSet = "name,speed".parse().unwrap();
+        let queries = [
+            ("name:bulbasaur", s.search(&SearchQuery::nat_dex(1))),
+            ("nothing", PokedexSearchResult::default()),
+            ("1,4", s.search(&SearchQuery::nat_dex(4))),
+        ];
+        let write = |write_type| {
+            let mut writer = Vec::new();
+            let mut batch = BatchWriter::new(write_type, fields.clone(), false).unwrap();
+            batch.begin(&mut writer).unwrap();
+            for (query, result) in &queries {
+                batch.write(&mut writer, query, result).unwrap();
+            }
+            String::from_utf8(writer).unwrap()
+        };
+        assert_eq!(
+            write(WriteType::Csv),
+            "query,name,speed\nname:bulbasaur,Bulbasaur,45\n\"1,4\",Charmander,65\n"
+        );
+        assert_eq!(
+            write(WriteType::Jsonl),
+            "{\"query\":\"name:bulbasaur\",\"name\":\"Bulbasaur\",\"speed\":\"45\"}\n\
+             {\"query\":\"1,4\",\"name\":\"Charmander\",\"speed\":\"65\"}"
+        );
+        assert!(BatchWriter::new(WriteType::Json, fields, false).is_none());
+    }
+    #[test]
+    fn test_html_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        let fields = "name,type1,type2,bst".parse().unwrap();
+        s.search(&SearchQuery::nat_dex(6))
+            .write_data(&mut writer, WriteType::Html.writer(fields, false).as_mut())
+            .unwrap();
+        let html = String::from_utf8(writer).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<tr><td>Charizard</td>\
+             <td><span class=\"type\" style=\"background:#ee8130\">Fire</span></td>\
+             <td><span class=\"type\" style=\"background:#a98ff3\">Flying</span></td>\
+             <td class=\"numeric\">534</td></tr>"
+        ));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(!html.contains("http"));
+        assert_eq!(escape_html("<a & 'b'>"), "&lt;a &amp; &#39;b&#39;&gt;");
+        assert!(matches!("html".parse(), Ok(WriteType::Html)));
+    }
+    #[test]
+    fn test_yaml_and_toml_round_trip() {
+        #[derive(serde::Deserialize)]
+        struct TomlDoc {
+            pokemon: Vec<Pokemon>,
+        }
+        let s = PokeDexMmap::new().unwrap();
+        let result = s.search(&SearchQuery::Range(std::range::Range::from(770..774)));
+
+        let mut yaml = Vec::new();
+        result
+            .write_data(&mut yaml, WriteType::Yaml.writer(0.into(), false).as_mut())
+            .unwrap();
+        let yaml: Vec<Pokemon> = serde_yaml_ng::from_slice(&yaml).unwrap();
+
+        let mut toml = Vec::new();
+        result
+            .write_data(&mut toml, WriteType::Toml.writer(0.into(), false).as_mut())
+            .unwrap();
+        let toml: TomlDoc = toml::from_slice(&toml).unwrap();
+
+        assert_eq!(yaml, result.to_vec());
+        assert_eq!(toml.pokemon, yaml);
+        assert!(matches!("yml".parse(), Ok(WriteType::Yaml)));
+    }
+    #[test]
+    fn test_only_typed_writers_use_kebab_case() {
+        let s = PokeDexMmap::new().unwrap();
+        let charizard = s.search(&SearchQuery::nat_dex(6));
+        let json = || serde_json::to_string(&charizard.pokemon()[0]).unwrap();
+        let before = json();
+        assert!(before.contains("\"SolarPower\"") && before.contains("\"Flame pokémon\""));
+        let mut yaml = Vec::new();
+        charizard
+            .write_data(&mut yaml, WriteType::Yaml.writer(0.into(), false).as_mut())
+            .unwrap();
+        let yaml = String::from_utf8(yaml).unwrap();
+        assert!(
+            yaml.contains("hidden_ability: solar-power") && yaml.contains("genus: flame pokémon")
+        );
+        //serializing afterwards isn't affected by the yaml writer
+        assert_eq!(json(), before);
+    }
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let path = std::env::temp_dir().join("rsdex_test_sqlite_writer.sqlite");
+        let mut file = std::fs::File::create(&path).unwrap();
+        //charizard shows up twice from the or
+        let red_starters = KeyWord::and(
+            KeyWord::query(SearchQueryParsing::Color, "red").unwrap(),
+            KeyWord::query(SearchQueryParsing::Range, "2..6").unwrap(),
+        );
+        s.search_many(KeyWord::or(
+            KeyWord::query(SearchQueryParsing::NatDex, "6").unwrap(),
+            red_starters,
+        ))
+        .write_data(
+            &mut file,
+            WriteType::Sqlite.writer(0.into(), false).as_mut(),
+        )
+        .unwrap();
+        drop(file);
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let count = |table: &str| -> u32 {
+            connection
+                .query_row(&format!("SELECT count(*) FROM {table}"), [], |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+        assert_eq!(count("pokemon"), 3);
+        assert_eq!(count("pokemon_type"), 4);
+        assert_eq!(count("pokemon_ability"), 6);
+        assert_eq!(count("pokemon_egg_group"), 6);
+        let hidden: String = connection
+            .query_row(
+                "SELECT ability FROM pokemon_ability WHERE national_dex_number = 6 AND is_hidden",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hidden, "solar-power");
+        std::fs::remove_file(path).unwrap();
+    }
+    #[cfg(feature = "columnar")]
+    #[test]
+    fn test_arrow_writer() {
+        use arrow_array::{Array, UInt8Array, UInt16Array};
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        s.search(&SearchQuery::NatDex(NationalPokedexNumber::new(25).unwrap()))
+            .write_data(
+                &mut writer,
+                WriteType::Arrow
+                    .writer("num,name,type2,speed".parse().unwrap(), false)
+                    .as_mut(),
+            )
+            .unwrap();
+        let batch = arrow_ipc::reader::FileReader::try_new(std::io::Cursor::new(writer), None)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.num_rows(), 1);
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let dex_num = column("national_dex_number");
+        let dex_num = dex_num.as_any().downcast_ref::<UInt16Array>().unwrap();
+        assert_eq!(dex_num.value(0), 25);
+        let speed = column("speed");
+        assert_eq!(speed.as_any().downcast_ref::<UInt8Array>().unwrap().value(0), 90);
+        assert!(column("type2").is_null(0));
+    }
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_writer() {
+        use crate::writing::Compression;
+        use std::{io::Read, path::Path};
+
+        let (compression, inner) = Compression::from_path(Path::new("out/dex.jsonl.zst")).unwrap();
+        assert_eq!(compression, Compression::Zstd);
+        assert_eq!(inner, Path::new("out/dex.jsonl"));
+        assert!(Compression::from_path(Path::new("dex.jsonl")).is_none());
+
+        let s = PokeDexMmap::new().unwrap();
+        let result = s.search(&SearchQuery::nat_dex(25));
+        let mut compressed = Compression::Gzip.writer(Vec::new()).unwrap();
+        result
+            .write_data(
+                &mut compressed,
+                WriteType::Csv.writer(0.into(), false).as_mut(),
+            )
+            .unwrap();
+        let compressed = compressed.finish().unwrap();
+        let mut csv = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert_eq!(csv, "name,national dex number\nPikachu,25\n");
+    }
+    #[test]
+    fn test_split_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let dir = std::env::temp_dir().join("rsdex_test_split_writer");
+        let _ = std::fs::remove_dir_all(&dir);
+        let result = s.search_many(KeyWord::or(
+            KeyWord::query(SearchQueryParsing::Name, "mr-mime").unwrap(),
+            KeyWord::query(SearchQueryParsing::Type, "ghost").unwrap(),
+        ));
+        let paths = SplitWriter::new(&dir, Field::Generation, WriteType::Csv, 0.into(), false)
+            .write(&result)
+            .unwrap();
+        assert_eq!(paths.len(), 9);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("1.csv")).unwrap(),
+            "name,national dex number\nMrMime,122\nGastly,92\nHaunter,93\nGengar,94\n"
+        );
+        let by_name = SplitWriter::new(&dir, Field::Name, WriteType::Json, 0.into(), false);
+        by_name
+            .write(&s.search(&SearchQuery::nat_dex(122)))
+            .unwrap();
+        assert!(dir.join("mrmime.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn test_read_back() {
+        let s = PokeDexMmap::new().unwrap();
+        let result = s.search_many(KeyWord::or(
+            KeyWord::query(SearchQueryParsing::Type, "dragon").unwrap(),
+            KeyWord::query(SearchQueryParsing::Name, "mr-mime").unwrap(),
+        ));
+        let empty = VecPokedex::new(Vec::new());
+        for write_type in [WriteType::Csv, WriteType::Json, WriteType::Jsonl] {
+            let mut writer = Vec::new();
+            result
+                .write_data(
+                    &mut writer,
+                    write_type.writer(FieldSet::all(), true).as_mut(),
+                )
+                .unwrap();
+            let read_type = write_type.to_string().parse().unwrap();
+            //every field is there so nothing has to be filled in
+            let read = read_pokemon(writer.as_slice(), read_type, &empty).unwrap();
+            assert_eq!(PokedexSearchResult::new(read), result);
+        }
+
+        let edited = "name,speed,type2\r\nDratini,\"150\",Fairy\r\n";
+        let read = read_pokemon(edited.as_bytes(), ReadType::Csv, &s).unwrap();
+        assert_eq!(read[0].get_stats()[5], 150);
+        assert_eq!(read[0].get_field(Field::Type2), "Fairy");
+        assert_eq!(read[0].get_field(Field::Genus), "Dragon pokémon");
+        let error = read_pokemon(edited.as_bytes(), ReadType::Csv, &empty).unwrap_err();
+        assert!(error.to_string().starts_with("record 1:"));
+    }
+    #[test]
+    fn test_custom_writer() {
+        let s = PokeDexMmap::new().unwrap();
+        let mut writer = Vec::new();
+        s.search(&SearchQuery::Range(std::range::Range::from(0..4)))
+            .write_data(&mut writer, &mut NameWriter)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "<Bulbasaur;Ivysaur;Venusaur;>"
+        )
+    }
 }
@@ -1,15 +1,20 @@
 use crate::{
+    color::Painter,
     data_types::{
         EggGroup, NationalPokedexNumber, PokedexColor, PokemonAbility, PokemonName, PokemonType,
         StatWithOrder,
     },
+    fields::{Field, FieldSet},
     pokemon::Pokemon,
     search::{KeyWord, SearchQuery},
 };
 use memmap2::Mmap;
+use strsim::damerau_levenshtein;
 // use rayon::iter::{ParallelBridge, ParallelIterator};
 // use serde::Deserialize;
 #[cfg(feature = "file_writing")]
+use crate::writing::PokemonWriter;
+#[cfg(feature = "file_writing")]
 use std::io::{self, Write};
 use std::{
     // fs::File,
@@ -48,19 +53,68 @@ impl PokedexSearchResult {
         self.vec
             .sort_by(|o, t| o.get_dex_number().cmp(t.get_dex_number()));
     }
+    ///sorts by `field`, numbers by their value and the rest alphabetically, ties stay in dex order
+    pub fn sort_by_field(&mut self, field: Field, descending: bool) {
+        let key = |pokemon: &Pokemon| {
+            let value = pokemon.get_field(field);
+            if field.is_numeric() {
+                (value.parse::<u16>().unwrap_or(0), String::new())
+            } else {
+                (0, value.to_lowercase())
+            }
+        };
+        self.sort();
+        if descending {
+            self.vec
+                .sort_by_cached_key(|pokemon| std::cmp::Reverse(key(pokemon)));
+        } else {
+            self.vec.sort_by_cached_key(key);
+        }
+    }
 
-    pub fn print_data(&self, detail_level: u8) {
+    ///with `stat_bars` the stats are drawn as bars after the other fields
+    pub fn print_data(&self, fields: &FieldSet, painter: &Painter, stat_bars: bool) {
         // let vec = self.to_vec();
         if self.vec.is_empty() {
             println!("sorry we couldn't find any thing in our data");
             return;
         }
+        println!("{}", self.render_data(fields, painter, stat_bars))
+    }
+    ///what [`print_data`](Self::print_data) prints for results that aren't empty
+    pub fn render_data(&self, fields: &FieldSet, painter: &Painter, stat_bars: bool) -> String {
         let mut out = String::new();
         for pokemon in &self.vec {
-            out += &pokemon.get_display(detail_level);
+            if stat_bars {
+                out += &pokemon.get_painted_display(&fields.without_stats(), painter);
+                out += &pokemon.get_stat_bars(painter);
+            } else {
+                out += &pokemon.get_painted_display(fields, painter);
+            }
             out += "\n"
         }
-        println!("{out}")
+        out
+    }
+    ///groups the results by the value of `field`, groups and the pokemon in them keep the order they were found in
+    pub fn split_by(&self, field: Field) -> Vec<(String, PokedexSearchResult)> {
+        let mut groups: Vec<(String, PokedexSearchResult)> = Vec::new();
+        for pokemon in &self.vec {
+            let key = pokemon.get_field(field);
+            match groups.iter_mut().find(|(group, _)| *group == key) {
+                Some((_, group)) => group.vec.push(pokemon.clone()),
+                None => groups.push((key, PokedexSearchResult::new(vec![pokemon.clone()]))),
+            }
+        }
+        groups
+    }
+    pub fn pokemon(&self) -> &[Pokemon] {
+        &self.vec
+    }
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
     }
     pub fn get_if_single(&self) -> Option<&Pokemon> {
         if self.vec.len() == 1 {
@@ -70,39 +124,12 @@ impl PokedexSearchResult {
         }
     }
     #[cfg(feature = "file_writing")]
-    pub fn write_data<W: Write>(
+    pub fn write_data<W: Write, P: PokemonWriter + ?Sized>(
         &self,
         writer: &mut W,
-        detail_level: u8,
-        write_mode: crate::WriteType,
-        pretty: bool,
+        pokemon_writer: &mut P,
     ) -> io::Result<()> {
-        // println!("writing to {}", file_path.display());
-        // // let fp = Path::new(&fp);
-        // let file = File::create(file_path)
-        //     .unwrap_or_else(|e| panic!("sorry rsdex could not create your file because {e}"));
-
-        // let mut writer = BufWriter::new(file);
-
-        //tries to determine write mode if not set
-        // if write_mode.is_none() {
-        //     write_mode = match WriteMode::from_str(
-        //         file_path
-        //             .extension()
-        //             .unwrap_or_else(|| OsStr::new("extension missing"))
-        //             .to_str()
-        //             .expect("sorry the file path isn't valid unicode"),
-        //     ) {
-        //         Ok(w) => Some(w),
-        //         Err(_) => {
-        //             return Err(std::io::Error::other("could not guess writemode"));
-        //         }
-        //     }
-        // }
-
-        write_mode
-            // .expect("invailed write_mode state: still None")
-            .write(writer, &self.vec, detail_level, pretty)
+        pokemon_writer.write_pokemon(writer, &self.vec)
     }
     pub fn to_vec(self) -> Vec<Pokemon> {
         self.vec
@@ -186,6 +213,37 @@ impl Pokedex for PokeDexMmap {
     }
 }
 
+///a pokedex that is kept in memory, like a data set loaded with [`read_pokemon`](crate::reading::read_pokemon)
+pub struct VecPokedex {
+    pokemon: Vec<Pokemon>,
+}
+impl VecPokedex {
+    pub fn new(pokemon: Vec<Pokemon>) -> Self {
+        Self { pokemon }
+    }
+    pub fn pokemon(&self) -> &[Pokemon] {
+        &self.pokemon
+    }
+}
+impl Pokedex for VecPokedex {
+    fn find_single_pokemon<P: Fn(&Pokemon) -> bool + Sync + Send>(
+        &self,
+        find: P,
+    ) -> SingleSearchReturn {
+        self.pokemon.iter().find(|pokemon| find(pokemon)).cloned()
+    }
+    fn find_many_pokemon<P: Fn(&Pokemon) -> bool + Sync + Send>(
+        &self,
+        filter: P,
+    ) -> MultiSearchReturn {
+        self.pokemon
+            .iter()
+            .filter(|pokemon| filter(pokemon))
+            .cloned()
+            .collect()
+    }
+}
+
 pub trait Pokedex {
     fn find_many_pokemon<P: Fn(&Pokemon) -> bool + Sync + Send>(
         &self,
@@ -257,4 +315,47 @@ pub trait Pokedex {
             }
         }
     }
+    ///finds a pokemon from a dex number like `25` or `#25` or a name written any way,
+    ///`Mr. Mime`, `mr-mime` and `MrMime` all work
+    ///
+    ///the error has the closest names if there are any
+    fn resolve(&self, input: &str) -> Result<Pokemon, String> {
+        let input = input.trim();
+        if let Ok(number) = input.trim_start_matches('#').parse::<u16>() {
+            return self
+                .find_single_pokemon(|pokemon| pokemon.get_dex_number().number() == number)
+                .ok_or_else(|| format!("no pokemon has the dex number {number}"));
+        }
+        let key = name_key(input);
+        if let Some(pokemon) =
+            self.find_single_pokemon(|pokemon| name_key(&pokemon.get_name().to_kebab_case()) == key)
+        {
+            return Ok(pokemon);
+        }
+        let mut close: Vec<(usize, String)> = self
+            .find_many_pokemon(|_| true)
+            .iter()
+            .map(|pokemon| pokemon.get_name().to_kebab_case())
+            .map(|name| (damerau_levenshtein(&name_key(&name), &key), name))
+            .filter(|(distance, _)| *distance < 3)
+            .collect();
+        close.sort();
+        if close.is_empty() {
+            Err(format!("no pokemon is called '{input}'"))
+        } else {
+            let close: Vec<String> = close.into_iter().take(3).map(|(_, name)| name).collect();
+            Err(format!(
+                "no pokemon is called '{input}' did you mean: {}",
+                close.join(",")
+            ))
+        }
+    }
+}
+
+///a name with only its letters and digits in lowercase
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
 }
@@ -1,10 +1,14 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 
 use serde::Deserialize;
 
-use crate::data_types::{
-    BodyShape, EggGroup, NationalPokedexNumber, PokedexColor, PokemonAbility, PokemonGenus,
-    PokemonName, PokemonStat, PokemonType, StatWithOrder, stat_matches_ordering,
+use crate::{
+    color::Painter,
+    data_types::{
+        BodyShape, EggGroup, NationalPokedexNumber, PokedexColor, PokemonAbility, PokemonGenus,
+        PokemonName, PokemonStat, PokemonType, StatWithOrder, stat_matches_ordering,
+    },
+    fields::{Field, FieldSet},
 };
 // #[cfg(feature = "file_writing")]
 // #[derive(serde::Serialize)]
@@ -37,60 +41,153 @@ pub struct Pokemon {
 }
 impl Display for Pokemon {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.get_display(0))
+        write!(f, "{}", self.get_display(&FieldSet::from_detail_level(0)))
     }
 }
 impl Pokemon {
-    pub fn get_as_vec(&self, detail_level: u8) -> Vec<(&str, String)> {
-        #[rustfmt::skip]
-        let levels= [
-            (0_u8, ("name", self.name.to_string())),
-            (0, ("national dex number", self.national_dex_number.to_string())),
-            (1, ("genus", self.genus.to_string())),
-            (1, ("primary type", self.type1.to_string())),
-            (1, ("secondary type", self.type2.to_string())),
-            (2, ("color", self.color.to_string())),
-            (2, ("egg group 1", self.egg_group1.to_string())),
-            (2, ("egg group 2", self.egg_group2.to_string())),
-            (3,("ability 1",self.ability1.to_string())),
-            (3,("ability 2",self.ability2.to_string())),
-            (3,("hidden ability",self.hidden_ability.to_string())),
-            (3, ("shape", self.shape.to_string())),
-            (4, ("hp", self.hp.to_string())),
-            (4, ("attack", self.attack.to_string())),
-            (4, ("defence", self.defence.to_string())),
-            (4, ("special attack", self.special_attack.to_string())),
-            (4, ("special defence", self.special_defence.to_string())),
-            (4, ("speed", self.speed.to_string())),
-        ];
-
-        let mut vec = Vec::new();
-        for (level, data) in levels {
-            if level <= detail_level {
-                vec.push(data);
+    pub fn get_field(&self, field: Field) -> String {
+        match field {
+            Field::Name => self.name.to_string(),
+            Field::NationalDexNumber => self.national_dex_number.to_string(),
+            Field::Genus => self.genus.to_string(),
+            Field::Type1 => self.type1.to_string(),
+            Field::Type2 => self.type2.to_string(),
+            Field::Color => self.color.to_string(),
+            Field::EggGroup1 => self.egg_group1.to_string(),
+            Field::EggGroup2 => self.egg_group2.to_string(),
+            Field::Ability1 => self.ability1.to_string(),
+            Field::Ability2 => self.ability2.to_string(),
+            Field::HiddenAbility => self.hidden_ability.to_string(),
+            Field::Shape => self.shape.to_string(),
+            Field::Hp => self.hp.to_string(),
+            Field::Attack => self.attack.to_string(),
+            Field::Defence => self.defence.to_string(),
+            Field::SpecialAttack => self.special_attack.to_string(),
+            Field::SpecialDefence => self.special_defence.to_string(),
+            Field::Speed => self.speed.to_string(),
+            Field::Bst => self.base_stat_total().to_string(),
+            Field::Generation => self.national_dex_number.generation().to_string(),
+        }
+    }
+    ///changes `field` to `value`, which can be what [`get_field`](Self::get_field) gives or how the data set has it
+    ///
+    ///the base stat total and generation are worked out from the other fields so they are left alone
+    pub fn set_field(&mut self, field: Field, value: &str) -> Result<(), String> {
+        let value = match value.trim() {
+            //a cleared cell in a spreadsheet
+            "" => "None",
+            value => value,
+        };
+        match field {
+            Field::Name => self.name = PokemonName::from_display(value),
+            Field::NationalDexNumber => {
+                self.national_dex_number = value
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not a valid national dex number"))?
             }
+            Field::Genus => self.genus = PokemonGenus::from_display(value),
+            Field::Type1 => self.type1 = parse_value(field, value)?,
+            Field::Type2 => self.type2 = parse_value(field, value)?,
+            Field::Color => self.color = parse_value(field, value)?,
+            Field::EggGroup1 => self.egg_group1 = parse_value(field, value)?,
+            Field::EggGroup2 => self.egg_group2 = parse_value(field, value)?,
+            Field::Ability1 => self.ability1 = PokemonAbility::from_display(value),
+            Field::Ability2 => self.ability2 = PokemonAbility::from_display(value),
+            Field::HiddenAbility => self.hidden_ability = PokemonAbility::from_display(value),
+            Field::Shape => self.shape = parse_value(field, value)?,
+            Field::Hp => self.hp = parse_value(field, value)?,
+            Field::Attack => self.attack = parse_value(field, value)?,
+            Field::Defence => self.defence = parse_value(field, value)?,
+            Field::SpecialAttack => self.special_attack = parse_value(field, value)?,
+            Field::SpecialDefence => self.special_defence = parse_value(field, value)?,
+            Field::Speed => self.speed = parse_value(field, value)?,
+            Field::Bst | Field::Generation => (),
+        }
+        Ok(())
+    }
+    ///a pokemon with every field set to something, only meant to be filled in with [`set_field`](Self::set_field)
+    pub(crate) fn blank() -> Self {
+        Self {
+            name: PokemonName::new("None"),
+            national_dex_number: NationalPokedexNumber::new(1).expect("1 is a valid dex number"),
+            type1: PokemonType::None,
+            type2: PokemonType::None,
+            color: PokedexColor::Gray,
+            genus: PokemonGenus::new("None"),
+            ability1: PokemonAbility::null(),
+            ability2: PokemonAbility::null(),
+            hidden_ability: PokemonAbility::null(),
+            hp: 0,
+            attack: 0,
+            defence: 0,
+            special_attack: 0,
+            special_defence: 0,
+            speed: 0,
+            egg_group1: EggGroup::None,
+            egg_group2: EggGroup::None,
+            shape: BodyShape::Blob,
         }
+    }
+
+    pub fn get_as_vec(&self, fields: &FieldSet) -> Vec<(&'static str, String)> {
+        fields
+            .fields()
+            .iter()
+            .map(|field| (field.label(), self.get_field(*field)))
+            .collect()
+    }
 
-        vec
+    pub fn get_as_map(&self, fields: &FieldSet) -> HashMap<&str, String> {
+        HashMap::from_iter(self.get_as_vec(fields))
     }
 
-    pub fn get_as_map(&self, detail_level: u8) -> HashMap<&str, String> {
-        HashMap::from_iter(self.get_as_vec(detail_level))
+    pub fn get_display(&self, fields: &FieldSet) -> String {
+        self.get_painted_display(fields, &Painter::plain())
     }
 
-    pub fn get_display(&self, detail_level: u8) -> String {
+    ///same as [`Pokemon::get_display`] but with bold keys, type badges and colored stats
+    pub fn get_painted_display(&self, fields: &FieldSet, painter: &Painter) -> String {
         let mut data_string = String::new();
-        for (k, v) in self.get_as_vec(detail_level) {
+        for field in fields.fields() {
+            let v = self.get_field(*field);
             if &v != "None" {
-                data_string.push_str(&(k.to_owned() + ": " + &v + "\n"));
+                let v = match field {
+                    Field::Type1 => painter.type_badge(&self.type1),
+                    Field::Type2 => painter.type_badge(&self.type2),
+                    _ => painter.field(self, *field, &v),
+                };
+                data_string.push_str(&(painter.bold(field.label()) + ": " + &v + "\n"));
             }
-            // data_string.push_str(&v);
         }
-        // data_string.push('\n');
 
         data_string
     }
 
+    ///the base stats as bars with the base stat total at the end
+    pub fn get_stat_bars(&self, painter: &Painter) -> String {
+        let label_width = Field::STATS
+            .iter()
+            .map(|stat| stat.label().len())
+            .max()
+            .unwrap_or(0);
+        let mut bars = String::new();
+        for (field, stat) in Field::STATS.iter().zip(self.get_stats()) {
+            bars.push_str(&format!(
+                "{}{} {stat:>3} {}\n",
+                painter.bold(field.label()),
+                " ".repeat(label_width - field.label().len()),
+                painter.stat(stat, &stat_bar(stat)),
+            ));
+        }
+        let bst = self.base_stat_total().to_string();
+        bars.push_str(&format!(
+            "{}: {}\n",
+            painter.bold(Field::Bst.label()),
+            painter.base_stat_total(self.base_stat_total(), &bst)
+        ));
+        bars
+    }
+
     // pub fn print(&self, detail_level: u8) {
     //     println!("{}", self.get_display(detail_level));
     //     // println!("print data")
@@ -107,6 +204,12 @@ impl Pokemon {
     pub fn get_seconary_type(&self) -> &PokemonType {
         &self.type2
     }
+    pub fn get_genus(&self) -> &PokemonGenus {
+        &self.genus
+    }
+    pub fn get_shape(&self) -> &BodyShape {
+        &self.shape
+    }
     pub fn get_color(&self) -> &PokedexColor {
         &self.color
     }
@@ -125,6 +228,20 @@ impl Pokemon {
     pub fn get_hidden_ability(&self) -> &PokemonAbility {
         &self.hidden_ability
     }
+    ///hp, attack, defence, special attack, special defence and speed in that order
+    pub fn get_stats(&self) -> [u8; 6] {
+        [
+            self.hp,
+            self.attack,
+            self.defence,
+            self.special_attack,
+            self.special_defence,
+            self.speed,
+        ]
+    }
+    pub fn base_stat_total(&self) -> u16 {
+        self.get_stats().iter().map(|stat| *stat as u16).sum()
+    }
     pub fn stat_matches(&self, stat: &StatWithOrder) -> bool {
         let order = stat.operation;
         match stat.stat {
@@ -142,6 +259,27 @@ impl Pokemon {
     }
 }
 
+///how many characters a stat of 255 takes up
+const STAT_BAR_WIDTH: usize = 32;
+///a bar as long as the stat is proportional to the highest possible stat using eighth blocks
+fn stat_bar(stat: u8) -> String {
+    const PARTS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    let eighths = stat as usize * STAT_BAR_WIDTH * 8 / u8::MAX as usize;
+    "█".repeat(eighths / 8) + PARTS[eighths % 8]
+}
+
+///tries the way [`get_field`](Pokemon::get_field) writes it and then the way the data set does
+fn parse_value<T: FromStr + serde::de::DeserializeOwned>(
+    field: Field,
+    value: &str,
+) -> Result<T, String> {
+    value
+        .parse()
+        .ok()
+        .or_else(|| serde_json::from_value(serde_json::Value::String(value.to_string())).ok())
+        .ok_or_else(|| format!("'{value}' is not a valid {}", field.label()))
+}
+
 fn null_parser<'de, D, N: Nullable<'de>>(deserializer: D) -> Result<N, D::Error>
 where
     D: serde::Deserializer<'de>,
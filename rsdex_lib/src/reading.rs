@@ -0,0 +1,245 @@
+use std::{
+    fmt::Display,
+    io::{self, Read},
+    path::Path,
+};
+
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+use crate::{
+    Pokemon,
+    data_types::PokemonName,
+    fields::{Field, FieldSet},
+    pokedex::{Pokedex, VecPokedex},
+};
+
+///the files rsdex can read back in
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum ReadType {
+    Json,
+    Jsonl,
+    Csv,
+}
+impl ReadType {
+    ///guesses the read type from the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    ///the file extension isn't one of the [`ReadType`]s
+    UnknownReadType,
+    ///`record` counts from 1 and doesn't include the csv header
+    Invalid {
+        record: usize,
+        message: String,
+    },
+}
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{e}"),
+            ReadError::UnknownReadType => {
+                write!(f, "data sets have to be json, jsonl or csv files")
+            }
+            ReadError::Invalid { record, message } => write!(f, "record {record}: {message}"),
+        }
+    }
+}
+impl std::error::Error for ReadError {}
+impl From<io::Error> for ReadError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+///reads pokemon that were written by rsdex or edited afterwards
+///
+///keys can be the labels (`national dex number`) or the names used in the data set (`national_dex_number`)
+///and values can be written either way too. Fields that are left out are taken from the pokemon with the same
+///dex number or name in `base`, so a file written with a low detail level can still be read
+pub fn read_pokemon<R: Read, P: Pokedex>(
+    mut reader: R,
+    read_type: ReadType,
+    base: &P,
+) -> Result<Vec<Pokemon>, ReadError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = text.trim_start_matches('\u{feff}');
+    let records = match read_type {
+        ReadType::Json | ReadType::Jsonl => json_records(text)?,
+        ReadType::Csv => csv_records(text)?,
+    };
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(i, record)| {
+            build_pokemon(record, base).map_err(|message| ReadError::Invalid {
+                record: i + 1,
+                message,
+            })
+        })
+        .collect()
+}
+
+///loads a file as a [`VecPokedex`], the read type comes from the extension
+pub fn load_dataset<P: Pokedex>(path: &Path, base: &P) -> Result<VecPokedex, ReadError> {
+    let read_type = ReadType::from_path(path).ok_or(ReadError::UnknownReadType)?;
+    let file = std::fs::File::open(path)?;
+    Ok(VecPokedex::new(read_pokemon(
+        io::BufReader::new(file),
+        read_type,
+        base,
+    )?))
+}
+
+type Record = Vec<(String, String)>;
+
+///works for a json array, one object per line or pretty printed objects one after another
+fn json_records(text: &str) -> Result<Vec<Record>, ReadError> {
+    let mut records = Vec::new();
+    for value in serde_json::Deserializer::from_str(text).into_iter::<Value>() {
+        let value = value.map_err(|e| ReadError::Invalid {
+            record: records.len() + 1,
+            message: e.to_string(),
+        })?;
+        let objects = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        for object in objects {
+            let Value::Object(map) = object else {
+                return Err(ReadError::Invalid {
+                    record: records.len() + 1,
+                    message: "expected an object".into(),
+                });
+            };
+            records.push(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Value::String(value) => value,
+                            Value::Null => "None".into(),
+                            value => value.to_string(),
+                        };
+                        (key, value)
+                    })
+                    .collect(),
+            );
+        }
+    }
+    Ok(records)
+}
+
+fn csv_records(text: &str) -> Result<Vec<Record>, ReadError> {
+    let mut rows = csv_rows(text).into_iter();
+    let Some(header) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    rows.enumerate()
+        .map(|(i, row)| {
+            if row.len() != header.len() {
+                return Err(ReadError::Invalid {
+                    record: i + 1,
+                    message: format!(
+                        "has {} values but there are {} columns",
+                        row.len(),
+                        header.len()
+                    ),
+                });
+            }
+            Ok(header.iter().cloned().zip(row).collect())
+        })
+        .collect()
+}
+
+///splits csv into rows of cells, quoted cells can have commas, new lines and `""` for a quote in them
+fn csv_rows(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    //blank lines
+    rows.retain(|row| !(row.len() == 1 && row[0].trim().is_empty()));
+    rows
+}
+
+fn build_pokemon<P: Pokedex>(record: Record, base: &P) -> Result<Pokemon, String> {
+    let mut values = Vec::with_capacity(record.len());
+    for (key, value) in record {
+        let field = Field::from_label(&key).ok_or_else(|| format!("unknown field '{key}'"))?;
+        values.push((field, value));
+    }
+    let value_of = |field: Field| {
+        values
+            .iter()
+            .find(|(other, _)| *other == field)
+            .map(|(_, value)| value.trim())
+    };
+    let found = value_of(Field::NationalDexNumber)
+        .and_then(|num| base.find_by_natinal_dex_number(&num.parse().ok()?))
+        .or_else(|| {
+            value_of(Field::Name)
+                .and_then(|name| base.find_by_name(&PokemonName::from_display(name)))
+        });
+    let mut pokemon = match found {
+        Some(pokemon) => pokemon,
+        None => {
+            let missing: Vec<String> = FieldSet::all()
+                .fields()
+                .iter()
+                //worked out or allowed to be `None`
+                .filter(|field| {
+                    !matches!(
+                        field,
+                        Field::Bst
+                            | Field::Generation
+                            | Field::Type2
+                            | Field::EggGroup2
+                            | Field::Ability2
+                            | Field::HiddenAbility
+                    )
+                })
+                .filter(|field| value_of(**field).is_none())
+                .map(Field::to_string)
+                .collect();
+            if !missing.is_empty() {
+                return Err(format!(
+                    "no pokemon has its dex number or name so it needs every field but {} are missing",
+                    missing.join(",")
+                ));
+            }
+            Pokemon::blank()
+        }
+    };
+    for (field, value) in &values {
+        pokemon.set_field(*field, value)?;
+    }
+    Ok(pokemon)
+}
@@ -11,10 +11,23 @@ pub enum KeyWord {
 }
 impl KeyWord {
     pub fn parse(tokens: &mut impl Iterator<Item = String>) -> Result<KeyWord, String> {
-        let current = tokens.next().unwrap();
+        let Some(current) = tokens.next() else {
+            return Err("expected a search query like type:fire".into());
+        };
 
         let mut current_keyword = if let Some(current) = current.split_once(['=', ':']) {
-            let the_type = SearchQueryParsing::from_str(current.0).unwrap();
+            let the_type = SearchQueryParsing::from_str(current.0).map_err(|_| {
+                let suggestions = compute_similarity(current.0, &SearchQueryParsing::KEYS);
+                if suggestions.is_empty() {
+                    format!("unknown search key '{}'", current.0)
+                } else {
+                    format!(
+                        "unknown search key '{}' did you mean: {}",
+                        current.0,
+                        suggestions.join(",")
+                    )
+                }
+            })?;
             let current_search = current.1;
             KeyWord::query(the_type, current_search)?
         } else if let Some(num) = current.strip_prefix('#') {
@@ -144,5 +157,12 @@ impl FromStr for SearchQueryParsing {
     }
 }
 
+impl SearchQueryParsing {
+    ///the main name of every key
+    pub const KEYS: [&str; 8] = [
+        "name", "num", "type", "color", "stat", "egg", "range", "ability",
+    ];
+}
+
 #[derive(Debug)]
 pub struct UnknownSearchQueryKey;
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap, hash::{DefaultHasher, Hash, Hasher}, sync::{LazyLock, Mutex},
+    cell::Cell, collections::HashMap, hash::{DefaultHasher, Hash, Hasher}, sync::{LazyLock, Mutex},
 };
 
 use serde::{Deserialize, Serialize};
@@ -10,7 +10,7 @@ fn insert(value: String) -> Key {
     value.hash(&mut g);
     let id = g.finish();
     ID_MAP.lock().map(|mut m| m.insert(id, value)).unwrap();
-    return id;
+    id
 }
 fn get(id: &Key) -> Option<String> {
     ID_MAP.lock().map(|m| m.get(id).cloned()).unwrap()
@@ -28,7 +28,25 @@ fn id_to_str<D>(id: &u64, serializer: D) -> Result<D::Ok, D::Error>
 where
     D: serde::Serializer,
 {
-    get(id).serialize(serializer)
+    let value = get(id);
+    if KEBAB_CASE.get() {
+        value.map(make_kebab_case_from_camel).serialize(serializer)
+    } else {
+        value.serialize(serializer)
+    }
+}
+thread_local! {
+    static KEBAB_CASE: Cell<bool> = const { Cell::new(false) };
+}
+///serializes the ids the way the data set writes them while `f` runs, so `solar-power` instead of `SolarPower`
+///
+///the typed writers use it so what they write can be read back in
+#[cfg(feature = "file_writing")]
+pub(crate) fn kebab_case<T>(f: impl FnOnce() -> T) -> T {
+    KEBAB_CASE.set(true);
+    let out = f();
+    KEBAB_CASE.set(false);
+    out
 }
 fn from(value: &str) -> Key {
     let value = make_camel_case_from_kebab(value.to_lowercase());
@@ -37,18 +55,34 @@ fn from(value: &str) -> Key {
 }
 fn make_camel_case_from_kebab(mut kebab: String) -> String {
     fn capitalize_first_letter(mut name: String) -> String {
-        let first_letter = name.remove(0);
-        name.insert(0, first_letter.to_ascii_uppercase());
+        //half typed searches like `name:` can be empty
+        if !name.is_empty() {
+            let first_letter = name.remove(0);
+            name.insert(0, first_letter.to_ascii_uppercase());
+        }
         name
     }
     //replace the  `-`'s
     while let Some(dash_pos) = kebab.find("-") {
         kebab.remove(dash_pos);
-        let lower = kebab.remove(dash_pos);
-        kebab.insert(dash_pos, lower.to_ascii_uppercase());
+        if dash_pos < kebab.len() {
+            let lower = kebab.remove(dash_pos);
+            kebab.insert(dash_pos, lower.to_ascii_uppercase());
+        }
     }
     capitalize_first_letter(kebab)
 }
+///the reverse of [`make_camel_case_from_kebab`]
+fn make_kebab_case_from_camel(camel: String) -> String {
+    let mut kebab = String::with_capacity(camel.len());
+    for (i, c) in camel.chars().enumerate() {
+        if i != 0 && c.is_ascii_uppercase() {
+            kebab.push('-');
+        }
+        kebab.push(c.to_ascii_lowercase());
+    }
+    kebab
+}
 
 
 
@@ -61,7 +95,14 @@ impl StringId {
     pub fn new(value: &str) -> Self {
         Self(from(value))
     }
+    ///from what [`StringId::value`] gives back, so `SandVeil` and not `sandveil`
+    pub fn from_value(value: &str) -> Self {
+        Self::new(&make_kebab_case_from_camel(value.to_string()))
+    }
     pub fn value(&self) -> String {
         get(&self.0).expect("key was inserted when creating instance")
     }
+    pub fn kebab_value(&self) -> String {
+        make_kebab_case_from_camel(self.value())
+    }
 }
@@ -0,0 +1,169 @@
+use std::fmt::Display;
+
+use crate::{Pokemon, data_types::GENERATION_ENDS, pokedex::Pokedex, vocabulary::Vocabulary};
+
+///an overview of a data set, useful to check that a custom one has what it should
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    ///the lowest and highest dex number
+    pub dex_numbers: Option<(u16, u16)>,
+    ///dex numbers in between that no pokemon has, both ends are included
+    pub gaps: Vec<(u16, u16)>,
+    ///dex numbers more than one pokemon has
+    pub duplicates: Vec<u16>,
+    pub types: Vec<(String, usize)>,
+    ///every generation from 1 with how many pokemon were introduced in it
+    pub generations: Vec<(u8, usize)>,
+    pub abilities: usize,
+    pub extremes: Vec<Extreme>,
+}
+///the pokemon with the highest or lowest value of something
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extreme {
+    pub label: &'static str,
+    pub value: u16,
+    ///more than one if they are tied
+    pub pokemon: Vec<String>,
+}
+
+impl Summary {
+    pub fn new<P: Pokedex>(pokedex: &P) -> Self {
+        let mut all = pokedex.find_many_pokemon(|_| true);
+        all.sort_by_key(|pokemon| pokemon.get_dex_number().number());
+        let mut numbers: Vec<u16> = all
+            .iter()
+            .map(|pokemon| pokemon.get_dex_number().number())
+            .collect();
+        let mut duplicates: Vec<u16> = numbers
+            .windows(2)
+            .filter(|pair| pair[0] == pair[1])
+            .map(|pair| pair[0])
+            .collect();
+        duplicates.dedup();
+        numbers.dedup();
+        let gaps = numbers
+            .windows(2)
+            .filter(|pair| pair[1] > pair[0] + 1)
+            .map(|pair| (pair[0] + 1, pair[1] - 1))
+            .collect();
+        let generations = (1..=GENERATION_ENDS.len() as u8)
+            .map(|generation| {
+                let amount = all
+                    .iter()
+                    .filter(|pokemon| pokemon.get_dex_number().generation() == generation)
+                    .count();
+                (generation, amount)
+            })
+            .collect();
+        let bulk = |pokemon: &Pokemon| {
+            let stats = pokemon.get_stats();
+            stats[0] as u16 + stats[2] as u16 + stats[4] as u16
+        };
+        let speed = |pokemon: &Pokemon| pokemon.get_stats()[5] as u16;
+        let extremes = [
+            extreme(&all, "fastest", speed, true),
+            extreme(&all, "slowest", speed, false),
+            //hp and both defences
+            extreme(&all, "bulkiest", bulk, true),
+            extreme(
+                &all,
+                "highest base stat total",
+                Pokemon::base_stat_total,
+                true,
+            ),
+            extreme(
+                &all,
+                "lowest base stat total",
+                Pokemon::base_stat_total,
+                false,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        Self {
+            count: all.len(),
+            dex_numbers: numbers.first().zip(numbers.last()).map(|(a, b)| (*a, *b)),
+            gaps,
+            duplicates,
+            types: Vocabulary::Types.values(pokedex),
+            generations,
+            abilities: Vocabulary::Abilities.values(pokedex).len(),
+            extremes,
+        }
+    }
+}
+
+fn extreme(
+    all: &[Pokemon],
+    label: &'static str,
+    value_of: impl Fn(&Pokemon) -> u16,
+    highest: bool,
+) -> Option<Extreme> {
+    let values = all.iter().map(&value_of);
+    let value = if highest { values.max() } else { values.min() }?;
+    Some(Extreme {
+        label,
+        value,
+        pokemon: all
+            .iter()
+            .filter(|pokemon| value_of(pokemon) == value)
+            .map(|pokemon| pokemon.get_name().to_string())
+            .collect(),
+    })
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |values: Vec<String>| values.join(", ");
+        writeln!(f, "pokemon: {}", self.count)?;
+        match self.dex_numbers {
+            Some((first, last)) => writeln!(f, "dex numbers: {first} to {last}")?,
+            None => writeln!(f, "dex numbers: none")?,
+        }
+        let gaps = self
+            .gaps
+            .iter()
+            .map(|(first, last)| {
+                if first == last {
+                    first.to_string()
+                } else {
+                    format!("{first} to {last}")
+                }
+            })
+            .collect::<Vec<_>>();
+        if gaps.is_empty() {
+            writeln!(f, "gaps: none")?;
+        } else {
+            writeln!(f, "gaps: {}", join(gaps))?;
+        }
+        if !self.duplicates.is_empty() {
+            let duplicates = self.duplicates.iter().map(u16::to_string).collect();
+            writeln!(f, "duplicate dex numbers: {}", join(duplicates))?;
+        }
+        let types = self
+            .types
+            .iter()
+            .map(|(ptype, amount)| format!("{ptype} {amount}"))
+            .collect();
+        writeln!(f, "types: {}", join(types))?;
+        let generations = self
+            .generations
+            .iter()
+            .map(|(generation, amount)| format!("{generation}: {amount}"))
+            .collect();
+        writeln!(f, "generations: {}", join(generations))?;
+        writeln!(f, "distinct abilities: {}", self.abilities)?;
+        for extreme in &self.extremes {
+            writeln!(
+                f,
+                "{}: {} ({})",
+                extreme.label,
+                extreme.pokemon.join(", "),
+                extreme.value
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    Pokemon,
+    color::Painter,
+    fields::{Field, FieldSet},
+};
+
+///space between two columns
+const GAP: &str = "  ";
+///columns are never shrunk below this
+const MIN_WIDTH: usize = 4;
+
+///lays out pokemon in aligned columns, one row per pokemon
+///
+///if `max_width` is set the widest columns get shrunk until the table fits
+///and cells that are to long are cut off with a `…`
+pub fn render_table(
+    data: &[Pokemon],
+    fields: &FieldSet,
+    max_width: Option<usize>,
+    painter: &Painter,
+) -> String {
+    let header: Vec<String> = fields
+        .fields()
+        .iter()
+        .map(|field| field.label().to_string())
+        .collect();
+    let rows: Vec<Vec<String>> = data
+        .iter()
+        .map(|pokemon| {
+            fields
+                .fields()
+                .iter()
+                .map(|field| match pokemon.get_field(*field) {
+                    none if none == "None" => String::new(),
+                    value => value,
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|label| width_of(label)).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(width_of(cell));
+        }
+    }
+    if let Some(max_width) = max_width {
+        shrink_to_fit(&mut widths, max_width);
+    }
+
+    let mut table = String::new();
+    push_row(
+        &mut table,
+        &header,
+        &widths,
+        fields.fields(),
+        false,
+        |_, cell| painter.bold(cell),
+    );
+    let line: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+    table.push_str(&line.join(GAP));
+    table.push('\n');
+    for (pokemon, row) in data.iter().zip(&rows) {
+        push_row(
+            &mut table,
+            row,
+            &widths,
+            fields.fields(),
+            true,
+            |field, cell| painter.field(pokemon, field, cell),
+        );
+    }
+    table
+}
+
+///pads every cell to its width before `paint` is called so colors dont mess up the alignment
+fn push_row(
+    table: &mut String,
+    cells: &[String],
+    widths: &[usize],
+    fields: &[Field],
+    align: bool,
+    paint: impl Fn(Field, &str) -> String,
+) {
+    let mut row = Vec::with_capacity(cells.len());
+    for (i, ((cell, width), field)) in cells.iter().zip(widths).zip(fields).enumerate() {
+        let cell = truncate(cell, *width);
+        let mut cell = if align && field.is_numeric() {
+            format!("{cell:>width$}")
+        } else {
+            format!("{cell:<width$}")
+        };
+        //no trailing spaces
+        if i + 1 == cells.len() {
+            cell.truncate(cell.trim_end().len());
+        }
+        row.push(paint(*field, &cell));
+    }
+    table.push_str(&row.join(GAP));
+    table.push('\n');
+}
+
+fn shrink_to_fit(widths: &mut [usize], max_width: usize) {
+    let gaps = GAP.len() * widths.len().saturating_sub(1);
+    while widths.iter().sum::<usize>() + gaps > max_width {
+        let Some(widest) = widths.iter_mut().max() else {
+            return;
+        };
+        if *widest <= MIN_WIDTH {
+            return;
+        }
+        *widest -= 1;
+    }
+}
+
+fn truncate(cell: &str, width: usize) -> String {
+    if width_of(cell) <= width {
+        cell.to_string()
+    } else {
+        cell.chars().take(width - 1).chain(['…']).collect()
+    }
+}
+
+fn width_of(s: &str) -> usize {
+    s.chars().count()
+}
@@ -0,0 +1,181 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Pokemon, fields::Field};
+
+///a line of text with fields filled in for each pokemon
+///
+///`{name}` is replaced by the field, `{speed:>3}` pads it to 3 characters (`<` left, `>` right and `^` center,
+///numbers are right aligned if no side is given). `{?type2}/{type2}{/}` is only shown if the pokemon has a
+///secondary type and `{!type2}...{/}` only if it doesn't. `{{` and `}}` give literal braces
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    source: String,
+    parts: Vec<Part>,
+}
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Text(String),
+    Field {
+        field: Field,
+        align: Option<Align>,
+        width: usize,
+    },
+    Conditional {
+        field: Field,
+        present: bool,
+        body: Vec<Part>,
+    },
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+impl Template {
+    pub fn render(&self, pokemon: &Pokemon) -> String {
+        let mut out = String::new();
+        render_parts(&self.parts, pokemon, &mut out);
+        out
+    }
+}
+
+fn render_parts(parts: &[Part], pokemon: &Pokemon, out: &mut String) {
+    for part in parts {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Field {
+                field,
+                align,
+                width,
+            } => {
+                let value = pokemon.get_field(*field);
+                let align = align.unwrap_or(if field.is_numeric() {
+                    Align::Right
+                } else {
+                    Align::Left
+                });
+                let value = match align {
+                    Align::Left => format!("{value:<width$}"),
+                    Align::Right => format!("{value:>width$}"),
+                    Align::Center => format!("{value:^width$}"),
+                };
+                out.push_str(&value);
+            }
+            Part::Conditional {
+                field,
+                present,
+                body,
+            } => {
+                if (pokemon.get_field(*field) != "None") == *present {
+                    render_parts(body, pokemon, out);
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let mut stack: Vec<(Field, bool, Vec<Part>)> = Vec::new();
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err("unmatched '}' use '}}' for a literal brace".into()),
+                '{' => {
+                    let mut tag = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => tag.push(c),
+                            None => return Err(format!("'{{{tag}' is never closed")),
+                        }
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    let tag = tag.trim();
+                    if tag == "/" {
+                        let Some((field, present, outer)) = stack.pop() else {
+                            return Err("'{/}' without a '{?field}' or '{!field}'".into());
+                        };
+                        let body = std::mem::replace(&mut parts, outer);
+                        parts.push(Part::Conditional {
+                            field,
+                            present,
+                            body,
+                        });
+                    } else if let Some(name) = tag.strip_prefix('?') {
+                        stack.push((parse_field(name)?, true, std::mem::take(&mut parts)));
+                    } else if let Some(name) = tag.strip_prefix('!') {
+                        stack.push((parse_field(name)?, false, std::mem::take(&mut parts)));
+                    } else {
+                        parts.push(parse_field_part(tag)?);
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        if let Some((field, present, _)) = stack.last() {
+            let kind = if *present { '?' } else { '!' };
+            return Err(format!("'{{{kind}{field}}}' is never ended with '{{/}}'"));
+        }
+        Ok(Self {
+            source: s.to_string(),
+            parts,
+        })
+    }
+}
+
+///`name`, `name:10` or `name:>10`
+fn parse_field_part(tag: &str) -> Result<Part, String> {
+    let (name, spec) = tag.split_once(':').unwrap_or((tag, ""));
+    let field = parse_field(name)?;
+    let (align, width) = match spec.chars().next() {
+        Some('<') => (Some(Align::Left), &spec[1..]),
+        Some('>') => (Some(Align::Right), &spec[1..]),
+        Some('^') => (Some(Align::Center), &spec[1..]),
+        _ => (None, spec),
+    };
+    let width = if width.is_empty() {
+        0
+    } else {
+        width
+            .parse()
+            .map_err(|_| format!("'{spec}' is not a valid width for {field}"))?
+    };
+    Ok(Part::Field {
+        field,
+        align,
+        width,
+    })
+}
+
+fn parse_field(name: &str) -> Result<Field, String> {
+    //reuses the field list parsing for the suggestions
+    match crate::FieldSet::from_str(name)?.fields() {
+        [field] => Ok(*field),
+        _ => Err(format!("'{name}' is not a single field")),
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use strum::{Display, EnumIter, EnumString, VariantNames};
+
+use crate::{
+    Pokemon,
+    data_types::{BodyShape, EggGroup, PokedexColor, PokemonType},
+    pokedex::Pokedex,
+    search::SearchQueryParsing,
+};
+
+///the kinds of values pokemon have, for listing and completing what can be searched for
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames, EnumIter)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum Vocabulary {
+    Types,
+    Colors,
+    EggGroups,
+    Shapes,
+    Abilities,
+    Genera,
+    Names,
+}
+impl Vocabulary {
+    ///the key the values go after, shapes and genera can't be searched for
+    pub fn search_key(&self) -> Option<SearchQueryParsing> {
+        match self {
+            Vocabulary::Types => Some(SearchQueryParsing::Type),
+            Vocabulary::Colors => Some(SearchQueryParsing::Color),
+            Vocabulary::EggGroups => Some(SearchQueryParsing::EggGroup),
+            Vocabulary::Abilities => Some(SearchQueryParsing::Ability),
+            Vocabulary::Names => Some(SearchQueryParsing::Name),
+            Vocabulary::Shapes | Vocabulary::Genera => None,
+        }
+    }
+    pub fn from_search_key(key: SearchQueryParsing) -> Option<Self> {
+        match key {
+            SearchQueryParsing::Type => Some(Vocabulary::Types),
+            SearchQueryParsing::Color => Some(Vocabulary::Colors),
+            SearchQueryParsing::EggGroup => Some(Vocabulary::EggGroups),
+            SearchQueryParsing::Ability => Some(Vocabulary::Abilities),
+            SearchQueryParsing::Name => Some(Vocabulary::Names),
+            _ => None,
+        }
+    }
+    ///every value written the way it is searched for (`fire`, `arena-trap`, `mr-mime`) with how many pokemon
+    ///in `pokedex` have it
+    ///
+    ///the values of enums keep their order and are there even if no pokemon has them, the rest are sorted
+    pub fn values<P: Pokedex>(&self, pokedex: &P) -> Vec<(String, usize)> {
+        let all = pokedex.find_many_pokemon(|_| true);
+        let lowercase = |value: &dyn ToString| value.to_string().to_lowercase();
+        match self {
+            Vocabulary::Types => count_variants(&all, PokemonType::VARIANTS, |pokemon| {
+                vec![
+                    lowercase(pokemon.get_primary_type()),
+                    lowercase(pokemon.get_seconary_type()),
+                ]
+            }),
+            Vocabulary::Colors => count_variants(&all, PokedexColor::VARIANTS, |pokemon| {
+                vec![lowercase(pokemon.get_color())]
+            }),
+            Vocabulary::EggGroups => count_variants(&all, EggGroup::VARIANTS, |pokemon| {
+                vec![
+                    lowercase(pokemon.get_egg_group_1()),
+                    lowercase(pokemon.get_egg_group_2()),
+                ]
+            }),
+            Vocabulary::Shapes => count_variants(&all, BodyShape::VARIANTS, |pokemon| {
+                vec![lowercase(pokemon.get_shape())]
+            }),
+            Vocabulary::Abilities => count(&all, |pokemon| {
+                vec![
+                    pokemon.get_ability_1().to_kebab_case(),
+                    pokemon.get_ability_2().to_kebab_case(),
+                    pokemon.get_hidden_ability().to_kebab_case(),
+                ]
+            }),
+            Vocabulary::Genera => count(&all, |pokemon| vec![pokemon.get_genus().to_string()]),
+            Vocabulary::Names => count(&all, |pokemon| vec![pokemon.get_name().to_kebab_case()]),
+        }
+    }
+}
+
+///counts every value, a pokemon with the same value twice is counted once and `none` is left out
+fn count(all: &[Pokemon], values_of: impl Fn(&Pokemon) -> Vec<String>) -> Vec<(String, usize)> {
+    let mut counts = BTreeMap::new();
+    for pokemon in all {
+        let mut values = values_of(pokemon);
+        values.sort();
+        values.dedup();
+        for value in values.into_iter().filter(|value| value != "none") {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+    }
+    counts.into_iter().collect()
+}
+fn count_variants(
+    all: &[Pokemon],
+    variants: &[&str],
+    values_of: impl Fn(&Pokemon) -> Vec<String>,
+) -> Vec<(String, usize)> {
+    let counts = count(all, values_of);
+    variants
+        .iter()
+        .map(|variant| variant.to_lowercase())
+        .filter(|variant| variant != "none")
+        .map(|variant| {
+            let amount = counts
+                .iter()
+                .find(|(value, _)| *value == variant)
+                .map_or(0, |(_, amount)| *amount);
+            (variant, amount)
+        })
+        .collect()
+}
@@ -1,12 +1,37 @@
 #[cfg(feature = "file_writing")]
 use std::io::{self, Write};
 
-use strum::{Display, EnumString};
+use strum::{Display, EnumString, VariantNames};
 
 #[cfg(feature = "file_writing")]
-use crate::Pokemon;
+mod batch;
+#[cfg(feature = "columnar")]
+mod columnar;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "file_writing")]
+mod split;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
-#[derive(Clone, Display, EnumString, Default)]
+#[cfg(feature = "file_writing")]
+pub use batch::BatchWriter;
+#[cfg(feature = "compression")]
+pub use compression::{CompressedWriter, Compression};
+#[cfg(feature = "file_writing")]
+pub use split::SplitWriter;
+
+#[cfg(feature = "file_writing")]
+use crate::{
+    Pokemon,
+    color::type_color,
+    data_types::PokemonType,
+    fields::{Field, FieldSet},
+    string_id::kebab_case,
+    template::Template,
+};
+
+#[derive(Clone, Display, EnumString, VariantNames, Default)]
 #[strum(ascii_case_insensitive)]
 pub enum WriteType {
     Json,
@@ -14,90 +39,484 @@ pub enum WriteType {
     Csv,
     #[default]
     Txt,
+    #[strum(to_string = "Markdown", serialize = "md")]
+    Markdown,
+    #[strum(to_string = "Html", serialize = "htm")]
+    Html,
+    #[strum(to_string = "Yaml", serialize = "yml")]
+    Yaml,
+    Toml,
+    #[cfg(feature = "sqlite")]
+    #[strum(to_string = "Sqlite", serialize = "sqlite3", serialize = "db")]
+    Sqlite,
+    #[cfg(feature = "columnar")]
+    #[strum(to_string = "Parquet", serialize = "pq")]
+    Parquet,
+    ///the arrow ipc file format
+    #[cfg(feature = "columnar")]
+    #[strum(to_string = "Arrow", serialize = "ipc", serialize = "feather")]
+    Arrow,
 }
 #[cfg(feature = "file_writing")]
 impl WriteType {
+    ///makes the built in [`PokemonWriter`] for this write type
+    ///
+    ///yaml, toml and sqlite always write the whole [`Pokemon`] so they ignore `fields`
+    pub fn writer(&self, fields: FieldSet, pretty: bool) -> Box<dyn PokemonWriter> {
+        match self {
+            WriteType::Json => Box::new(JsonWriter::new(fields, pretty)),
+            WriteType::Jsonl => Box::new(JsonlWriter::new(fields, pretty)),
+            WriteType::Csv => Box::new(CsvWriter::new(fields)),
+            WriteType::Txt => Box::new(TxtWriter::new(fields)),
+            WriteType::Markdown => Box::new(MarkdownWriter::new(fields)),
+            WriteType::Html => Box::new(HtmlWriter::new(fields)),
+            WriteType::Yaml => Box::new(YamlWriter),
+            WriteType::Toml => Box::new(TomlWriter),
+            #[cfg(feature = "sqlite")]
+            WriteType::Sqlite => Box::new(sqlite::SqliteWriter::new()),
+            #[cfg(feature = "columnar")]
+            WriteType::Parquet => Box::new(columnar::ColumnarWriter::new(
+                columnar::ColumnarFormat::Parquet,
+                fields,
+            )),
+            #[cfg(feature = "columnar")]
+            WriteType::Arrow => Box::new(columnar::ColumnarWriter::new(
+                columnar::ColumnarFormat::ArrowIpc,
+                fields,
+            )),
+        }
+    }
+    ///a writer that carries on a file this write type already wrote to
+    ///
+    ///only jsonl and csv can be added to, csv leaves out the header and jsonl starts on a new line
+    ///unless the file `ends_with_new_line` already, like files from jq and most other tools do
+    pub fn append_writer(
+        &self,
+        fields: FieldSet,
+        pretty: bool,
+        ends_with_new_line: bool,
+    ) -> Option<Box<dyn PokemonWriter>> {
+        match self {
+            WriteType::Jsonl => Some(Box::new(JsonlWriter {
+                first: ends_with_new_line,
+                ..JsonlWriter::new(fields, pretty)
+            })),
+            WriteType::Csv => Some(Box::new(CsvWriter {
+                header: false,
+                ..CsvWriter::new(fields)
+            })),
+            _ => None,
+        }
+    }
+    ///the file extension that is used when rsdex picks the file name
+    pub fn extension(&self) -> &'static str {
+        match self {
+            WriteType::Json => "json",
+            WriteType::Jsonl => "jsonl",
+            WriteType::Csv => "csv",
+            WriteType::Txt => "txt",
+            WriteType::Markdown => "md",
+            WriteType::Html => "html",
+            WriteType::Yaml => "yaml",
+            WriteType::Toml => "toml",
+            #[cfg(feature = "sqlite")]
+            WriteType::Sqlite => "sqlite",
+            #[cfg(feature = "columnar")]
+            WriteType::Parquet => "parquet",
+            #[cfg(feature = "columnar")]
+            WriteType::Arrow => "arrow",
+        }
+    }
+    pub fn can_append(&self) -> bool {
+        matches!(self, WriteType::Jsonl | WriteType::Csv)
+    }
     pub fn write<W: Write>(
         &self,
         writer: &mut W,
         data: &[Pokemon],
-        detail_level: u8,
+        fields: FieldSet,
         pretty: bool,
     ) -> io::Result<()> {
+        self.writer(fields, pretty).write_pokemon(writer, data)
+    }
+}
+
+/// a output format that search results can be written in
+///
+/// `begin` is called once before the first pokemon, `record` once for every pokemon
+/// and `end` once after the last one
+#[cfg(feature = "file_writing")]
+pub trait PokemonWriter {
+    fn begin(&mut self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()>;
+    fn end(&mut self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+    ///runs all the hooks over `data`
+    fn write_pokemon(&mut self, writer: &mut dyn Write, data: &[Pokemon]) -> io::Result<()> {
         if data.is_empty() {
             return std::io::Result::Err(io::Error::other("data cant be empty"));
         }
+        self.begin(writer)?;
+        for pokemon in data {
+            self.record(writer, pokemon)?;
+        }
+        self.end(writer)
+    }
+}
 
-        match self {
-            WriteType::Json => {
-                //makes it a json array
-                writer.write_all("[".as_bytes())?;
-                let mut json_string = String::new();
-                for pkmn in data {
-                    let pkmap = &pkmn.get_as_map(detail_level);
-                    let pokemon_string = if pretty {
-                        serde_json::to_string_pretty(pkmap)?
-                    } else {
-                        serde_json::to_string(pkmap)?
-                    };
-                    json_string += (pokemon_string + ",").as_str();
-                    json_string.push('\n');
-                }
-                //removes the trailing comma
-                json_string.pop();
-                json_string.pop();
-                writer.write_all(json_string.as_bytes())?;
-                writer.write_all("]".as_bytes())?;
-            }
-            //def no copied from json
-            WriteType::Jsonl => {
-                let mut jsonl_string = String::new();
-                for pkmn in data {
-                    let pkmap = &pkmn.get_as_map(detail_level);
-                    let pokemon_string = if pretty {
-                        serde_json::to_string_pretty(pkmap)?
-                    } else {
-                        serde_json::to_string(pkmap)?
-                    };
-                    jsonl_string += (pokemon_string + "\n").as_str();
-                }
-                // no newline at end
-                jsonl_string.pop();
-                writer.write_all(jsonl_string.as_bytes())?;
-            }
-            WriteType::Csv => {
-                let mut csv_string = String::new();
-
-                for (column_name, _) in &data[0].get_as_vec(detail_level) {
-                    csv_string.push_str(column_name);
-                    csv_string.push(',');
-                }
-                csv_string.push('\n');
-
-                for pkmn in data {
-                    let vec = pkmn.get_as_vec(detail_level);
-
-                    for (_, column_value) in vec {
-                        csv_string.push_str(&column_value);
-                        csv_string.push(',');
-                    }
-                    csv_string.push('\n');
-                }
-                csv_string = csv_string.replace(",\n", "\n");
-                writer.write_all(csv_string.as_bytes())?;
-            }
-            WriteType::Txt => {
-                let mut txt_str = String::new();
-                for pokemon in data {
-                    for (key, value) in pokemon.get_as_vec(detail_level) {
-                        txt_str.push_str(&(key.to_string() + ":" + &value + "\n"));
-                    }
-                    txt_str.push('\n');
-                }
-                writer.write_all(txt_str.as_bytes())?;
+///serializes as a map that keeps the order of the fields
+#[cfg(feature = "file_writing")]
+struct OrderedFields<'a>(&'a [(&'static str, String)]);
+#[cfg(feature = "file_writing")]
+impl serde::Serialize for OrderedFields<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+    }
+}
+
+#[cfg(feature = "file_writing")]
+fn to_json_string(pokemon: &Pokemon, fields: &FieldSet, pretty: bool) -> io::Result<String> {
+    let pkmap = &OrderedFields(&pokemon.get_as_vec(fields));
+    Ok(if pretty {
+        serde_json::to_string_pretty(pkmap)?
+    } else {
+        serde_json::to_string(pkmap)?
+    })
+}
+
+#[cfg(feature = "file_writing")]
+struct JsonWriter {
+    fields: FieldSet,
+    pretty: bool,
+    first: bool,
+}
+#[cfg(feature = "file_writing")]
+impl JsonWriter {
+    fn new(fields: FieldSet, pretty: bool) -> Self {
+        Self {
+            fields,
+            pretty,
+            first: true,
+        }
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for JsonWriter {
+    fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        //makes it a json array
+        writer.write_all("[".as_bytes())
+    }
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        if !self.first {
+            writer.write_all(",\n".as_bytes())?;
+        }
+        self.first = false;
+        writer.write_all(to_json_string(pokemon, &self.fields, self.pretty)?.as_bytes())
+    }
+    fn end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all("]".as_bytes())
+    }
+}
+
+#[cfg(feature = "file_writing")]
+struct JsonlWriter {
+    fields: FieldSet,
+    pretty: bool,
+    first: bool,
+}
+#[cfg(feature = "file_writing")]
+impl JsonlWriter {
+    fn new(fields: FieldSet, pretty: bool) -> Self {
+        Self {
+            fields,
+            pretty,
+            first: true,
+        }
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for JsonlWriter {
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        // no newline at end
+        if !self.first {
+            writer.write_all("\n".as_bytes())?;
+        }
+        self.first = false;
+        writer.write_all(to_json_string(pokemon, &self.fields, self.pretty)?.as_bytes())
+    }
+}
+
+#[cfg(feature = "file_writing")]
+struct CsvWriter {
+    fields: FieldSet,
+    header: bool,
+}
+#[cfg(feature = "file_writing")]
+impl CsvWriter {
+    fn new(fields: FieldSet) -> Self {
+        Self {
+            fields,
+            header: true,
+        }
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for CsvWriter {
+    fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        if !self.header {
+            return Ok(());
+        }
+        let header: Vec<&str> = self.fields.fields().iter().map(Field::label).collect();
+        writer.write_all((header.join(",") + "\n").as_bytes())
+    }
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let row: Vec<String> = self
+            .fields
+            .fields()
+            .iter()
+            .map(|field| pokemon.get_field(*field))
+            .collect();
+        writer.write_all((row.join(",") + "\n").as_bytes())
+    }
+}
+
+#[cfg(feature = "file_writing")]
+struct TxtWriter {
+    fields: FieldSet,
+}
+#[cfg(feature = "file_writing")]
+impl TxtWriter {
+    fn new(fields: FieldSet) -> Self {
+        Self { fields }
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for TxtWriter {
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let mut txt_str = String::new();
+        for (key, value) in pokemon.get_as_vec(&self.fields) {
+            txt_str.push_str(&(key.to_string() + ":" + &value + "\n"));
+        }
+        txt_str.push('\n');
+        writer.write_all(txt_str.as_bytes())
+    }
+}
+
+///one rendered line per pokemon
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for Template {
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        writeln!(writer, "{}", self.render(pokemon))
+    }
+}
+
+///a github flavored markdown table
+#[cfg(feature = "file_writing")]
+struct MarkdownWriter {
+    fields: FieldSet,
+}
+#[cfg(feature = "file_writing")]
+impl MarkdownWriter {
+    fn new(fields: FieldSet) -> Self {
+        Self { fields }
+    }
+    fn write_row(writer: &mut dyn Write, cells: &[String]) -> io::Result<()> {
+        writer.write_all(("| ".to_string() + &cells.join(" | ") + " |\n").as_bytes())
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for MarkdownWriter {
+    fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        let fields = self.fields.fields();
+        let header: Vec<String> = fields
+            .iter()
+            .map(|field| escape_markdown(field.label()))
+            .collect();
+        Self::write_row(writer, &header)?;
+        let alignment: Vec<String> = fields
+            .iter()
+            .map(|field| if field.is_numeric() { "---:" } else { "---" }.to_string())
+            .collect();
+        Self::write_row(writer, &alignment)
+    }
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let row: Vec<String> = self
+            .fields
+            .fields()
+            .iter()
+            .map(|field| escape_markdown(&pokemon.get_field(*field)))
+            .collect();
+        Self::write_row(writer, &row)
+    }
+}
+
+///escapes anything that would end a cell or get formatted
+#[cfg(feature = "file_writing")]
+pub(crate) fn escape_markdown(cell: &str) -> String {
+    let mut escaped = String::with_capacity(cell.len());
+    for c in cell.chars() {
+        match c {
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '~' => {
+                escaped.push('\\');
+                escaped.push(c);
             }
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+///a yaml list of the typed [`Pokemon`]
+#[cfg(feature = "file_writing")]
+struct YamlWriter;
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for YamlWriter {
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        //a list of one so every record is a list item
+        let yaml = kebab_case(|| serde_yaml_ng::to_string(&[pokemon])).map_err(io::Error::other)?;
+        writer.write_all(yaml.as_bytes())
+    }
+}
+
+///a toml array of tables called `pokemon` with the typed [`Pokemon`] in it
+#[cfg(feature = "file_writing")]
+struct TomlWriter;
+#[cfg(feature = "file_writing")]
+#[derive(serde::Serialize)]
+struct TomlRecord<'a> {
+    pokemon: [&'a Pokemon; 1],
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for TomlWriter {
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let toml = kebab_case(|| toml::to_string(&TomlRecord { pokemon: [pokemon] }))
+            .map_err(io::Error::other)?;
+        writer.write_all((toml + "\n").as_bytes())
+    }
+}
+
+#[cfg(feature = "file_writing")]
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>rsdex results</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3rem 0.7rem; border-bottom: 1px solid #ddd; text-align: left; white-space: nowrap; }
+th { cursor: pointer; user-select: none; background: #f4f4f4; position: sticky; top: 0; }
+th.numeric, td.numeric { text-align: right; }
+th[data-order="asc"]::after { content: " \25B2"; }
+th[data-order="desc"]::after { content: " \25BC"; }
+tbody tr:hover { background: #f9f9f9; }
+.type { display: inline-block; min-width: 4.5rem; padding: 0.1rem 0.4rem; border-radius: 0.3rem; color: #fff; font-weight: bold; text-align: center; text-shadow: 0 1px 1px #0006; }
+</style>
+</head>
+<body>
+<table>
+"#;
+#[cfg(feature = "file_writing")]
+const HTML_TAIL: &str = r#"</tbody>
+</table>
+<script>
+document.querySelectorAll("th").forEach((header, column) => {
+    header.addEventListener("click", () => {
+        const body = document.querySelector("tbody");
+        const ascending = header.dataset.order !== "asc";
+        document.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+        header.dataset.order = ascending ? "asc" : "desc";
+        const numeric = header.classList.contains("numeric");
+        const rows = Array.from(body.rows);
+        rows.sort((a, b) => {
+            const left = a.cells[column].textContent;
+            const right = b.cells[column].textContent;
+            const order = numeric ? left - right : left.localeCompare(right);
+            return ascending ? order : -order;
+        });
+        rows.forEach((row) => body.appendChild(row));
+    });
+});
+</script>
+</body>
+</html>
+"#;
+
+///a html page with a table that can be sorted by clicking the headers, everything is inline
+#[cfg(feature = "file_writing")]
+struct HtmlWriter {
+    fields: FieldSet,
+}
+#[cfg(feature = "file_writing")]
+impl HtmlWriter {
+    fn new(fields: FieldSet) -> Self {
+        Self { fields }
+    }
+    fn class(field: &Field) -> &'static str {
+        if field.is_numeric() {
+            r#" class="numeric""#
+        } else {
+            ""
+        }
+    }
+    fn type_badge(ptype: &PokemonType) -> String {
+        if *ptype == PokemonType::None {
+            return String::new();
+        }
+        let (r, g, b) = type_color(ptype);
+        format!(r#"<span class="type" style="background:#{r:02x}{g:02x}{b:02x}">{ptype}</span>"#)
+    }
+}
+#[cfg(feature = "file_writing")]
+impl PokemonWriter for HtmlWriter {
+    fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        let mut head = String::from(HTML_HEAD);
+        head.push_str("<thead><tr>");
+        for field in self.fields.fields() {
+            head.push_str(&format!(
+                "<th{}>{}</th>",
+                Self::class(field),
+                escape_html(field.label())
+            ));
         }
+        head.push_str("</tr></thead>\n<tbody>\n");
+        writer.write_all(head.as_bytes())
+    }
+    fn record(&mut self, writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let mut row = String::from("<tr>");
+        for field in self.fields.fields() {
+            let cell = match field {
+                Field::Type1 => Self::type_badge(pokemon.get_primary_type()),
+                Field::Type2 => Self::type_badge(pokemon.get_seconary_type()),
+                _ => escape_html(&pokemon.get_field(*field)),
+            };
+            row.push_str(&format!("<td{}>{cell}</td>", Self::class(field)));
+        }
+        row.push_str("</tr>\n");
+        writer.write_all(row.as_bytes())
+    }
+    fn end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(HTML_TAIL.as_bytes())
+    }
+}
 
-        Ok(())
+#[cfg(feature = "file_writing")]
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
     }
+    escaped
 }
@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use crate::{
+    fields::{Field, FieldSet},
+    pokedex::PokedexSearchResult,
+    writing::{OrderedFields, WriteType},
+};
+
+///writes the results of many queries into one csv or jsonl with a `query` column in front
+///
+///`begin` writes the csv header once and `write` is called for every query
+pub struct BatchWriter {
+    write_type: WriteType,
+    fields: FieldSet,
+    pretty: bool,
+    first: bool,
+}
+impl BatchWriter {
+    ///only csv and jsonl have columns a query can go into
+    pub fn new(write_type: WriteType, fields: FieldSet, pretty: bool) -> Option<Self> {
+        matches!(write_type, WriteType::Csv | WriteType::Jsonl).then_some(Self {
+            write_type,
+            fields,
+            pretty,
+            first: true,
+        })
+    }
+    pub fn begin(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        if !matches!(self.write_type, WriteType::Csv) {
+            return Ok(());
+        }
+        let header: Vec<&str> = std::iter::once("query")
+            .chain(self.fields.fields().iter().map(Field::label))
+            .collect();
+        writer.write_all((header.join(",") + "\n").as_bytes())
+    }
+    ///a query without results writes nothing
+    pub fn write(
+        &mut self,
+        writer: &mut dyn Write,
+        query: &str,
+        result: &PokedexSearchResult,
+    ) -> io::Result<()> {
+        for pokemon in result.pokemon() {
+            match self.write_type {
+                WriteType::Csv => {
+                    let row: Vec<String> = std::iter::once(escape_csv(query))
+                        .chain(
+                            self.fields
+                                .fields()
+                                .iter()
+                                .map(|field| pokemon.get_field(*field)),
+                        )
+                        .collect();
+                    writer.write_all((row.join(",") + "\n").as_bytes())?;
+                }
+                _ => {
+                    //like jsonl there is no newline at the end
+                    if !self.first {
+                        writer.write_all("\n".as_bytes())?;
+                    }
+                    let mut record = vec![("query", query.to_string())];
+                    record.extend(pokemon.get_as_vec(&self.fields));
+                    let record = &OrderedFields(&record);
+                    let json = if self.pretty {
+                        serde_json::to_string_pretty(record)?
+                    } else {
+                        serde_json::to_string(record)?
+                    };
+                    writer.write_all(json.as_bytes())?;
+                }
+            }
+            self.first = false;
+        }
+        Ok(())
+    }
+}
+
+///queries are typed by people so they get quoted if they need to be
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
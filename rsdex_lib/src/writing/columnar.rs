@@ -0,0 +1,137 @@
+use std::{
+    io::{self, Write},
+    sync::Arc,
+};
+
+use arrow_array::{
+    ArrayRef, DictionaryArray, RecordBatch, StringArray, UInt8Array, UInt16Array, types::Int16Type,
+};
+use arrow_schema::{DataType, Field as ArrowField, Schema};
+
+use crate::{
+    Pokemon,
+    fields::{Field, FieldSet},
+    writing::PokemonWriter,
+};
+
+#[derive(Clone, Copy)]
+pub(super) enum ColumnarFormat {
+    Parquet,
+    ArrowIpc,
+}
+
+///collects every pokemon and writes them as one record batch at the end
+///
+///dex numbers are u16, stats are u8 and the things with few values like types and abilities
+///are dictionary encoded with `None` as null
+pub(super) struct ColumnarWriter {
+    format: ColumnarFormat,
+    fields: FieldSet,
+    data: Vec<Pokemon>,
+}
+impl ColumnarWriter {
+    pub(super) fn new(format: ColumnarFormat, fields: FieldSet) -> Self {
+        Self {
+            format,
+            fields,
+            data: Vec::new(),
+        }
+    }
+    fn data_type(field: &Field) -> DataType {
+        match field {
+            Field::NationalDexNumber | Field::Bst => DataType::UInt16,
+            Field::Generation => DataType::UInt8,
+            _ if field.is_stat() => DataType::UInt8,
+            Field::Name | Field::Genus => DataType::Utf8,
+            _ => DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8)),
+        }
+    }
+    fn column(&self, field: &Field) -> ArrayRef {
+        let data = &self.data;
+        match Self::data_type(field) {
+            DataType::UInt16 => Arc::new(UInt16Array::from_iter_values(data.iter().map(
+                |pokemon| match field {
+                    Field::Bst => pokemon.base_stat_total(),
+                    _ => pokemon.get_dex_number().number(),
+                },
+            ))),
+            DataType::UInt8 => {
+                let stat = match field {
+                    Field::Hp => 0,
+                    Field::Attack => 1,
+                    Field::Defence => 2,
+                    Field::SpecialAttack => 3,
+                    Field::SpecialDefence => 4,
+                    _ => 5,
+                };
+                Arc::new(UInt8Array::from_iter_values(data.iter().map(
+                    |pokemon| match field {
+                        Field::Generation => pokemon.get_dex_number().generation(),
+                        _ => pokemon.get_stats()[stat],
+                    },
+                )))
+            }
+            DataType::Utf8 => Arc::new(StringArray::from_iter_values(
+                data.iter().map(|pokemon| pokemon.get_field(*field)),
+            )),
+            _ => {
+                let values: Vec<String> = data
+                    .iter()
+                    .map(|pokemon| pokemon.get_field(*field))
+                    .collect();
+                Arc::new(
+                    values
+                        .iter()
+                        .map(|value| (value != "None").then_some(value.as_str()))
+                        .collect::<DictionaryArray<Int16Type>>(),
+                )
+            }
+        }
+    }
+    fn record_batch(&self) -> io::Result<RecordBatch> {
+        let schema = Schema::new(
+            self.fields
+                .fields()
+                .iter()
+                .map(|field| {
+                    let nullable = matches!(Self::data_type(field), DataType::Dictionary(..));
+                    ArrowField::new(field.to_string(), Self::data_type(field), nullable)
+                })
+                .collect::<Vec<_>>(),
+        );
+        let columns = self
+            .fields
+            .fields()
+            .iter()
+            .map(|field| self.column(field))
+            .collect();
+        RecordBatch::try_new(Arc::new(schema), columns).map_err(io::Error::other)
+    }
+}
+
+impl PokemonWriter for ColumnarWriter {
+    fn record(&mut self, _writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        self.data.push(pokemon.clone());
+        Ok(())
+    }
+    fn end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        let batch = self.record_batch()?;
+        let mut buffer = Vec::new();
+        match self.format {
+            ColumnarFormat::Parquet => {
+                let mut parquet =
+                    parquet::arrow::ArrowWriter::try_new(&mut buffer, batch.schema(), None)
+                        .map_err(io::Error::other)?;
+                parquet.write(&batch).map_err(io::Error::other)?;
+                parquet.close().map_err(io::Error::other)?;
+            }
+            ColumnarFormat::ArrowIpc => {
+                let mut ipc = arrow_ipc::writer::FileWriter::try_new(&mut buffer, &batch.schema())
+                    .map_err(io::Error::other)?;
+                ipc.write(&batch).map_err(io::Error::other)?;
+                ipc.finish().map_err(io::Error::other)?;
+            }
+        }
+        writer.write_all(&buffer)
+    }
+}
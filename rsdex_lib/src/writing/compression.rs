@@ -0,0 +1,66 @@
+use std::{
+    ffi::OsStr,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use strum::{Display, EnumString};
+
+///a compression that can be put around any writer
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum Compression {
+    #[strum(to_string = "gz", serialize = "gzip")]
+    Gzip,
+    #[strum(to_string = "zst", serialize = "zstd")]
+    Zstd,
+}
+impl Compression {
+    ///the compression of a path like `dex.jsonl.zst` and the path without it, `dex.jsonl`
+    pub fn from_path(path: &Path) -> Option<(Self, PathBuf)> {
+        let compression = path.extension().and_then(OsStr::to_str)?.parse().ok()?;
+        Some((compression, path.with_extension("")))
+    }
+    ///wraps `writer` so everything written to it gets compressed
+    ///
+    ///[`CompressedWriter::finish`] has to be called once done or the end of the stream is lost
+    pub fn writer<W: Write>(&self, writer: W) -> io::Result<CompressedWriter<W>> {
+        Ok(CompressedWriter(match self {
+            Compression::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            Compression::Zstd => Encoder::Zstd(zstd::Encoder::new(writer, 0)?),
+        }))
+    }
+}
+
+///a writer made by [`Compression::writer`]
+pub struct CompressedWriter<W: Write>(Encoder<W>);
+enum Encoder<W: Write> {
+    Gzip(flate2::write::GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+}
+impl<W: Write> CompressedWriter<W> {
+    ///writes the end of the stream and gives back the inner writer
+    pub fn finish(self) -> io::Result<W> {
+        match self.0 {
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.0 {
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
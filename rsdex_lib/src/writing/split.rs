@@ -0,0 +1,75 @@
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    fields::{Field, FieldSet},
+    pokedex::PokedexSearchResult,
+    writing::WriteType,
+};
+
+///writes a search result as one file per value of a field
+///
+///splitting by `type1` into `out` gives `out/fire.csv`, `out/water.csv` and so on
+pub struct SplitWriter {
+    dir: PathBuf,
+    split_by: Field,
+    write_type: WriteType,
+    fields: FieldSet,
+    pretty: bool,
+}
+impl SplitWriter {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        split_by: Field,
+        write_type: WriteType,
+        fields: FieldSet,
+        pretty: bool,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            split_by,
+            write_type,
+            fields,
+            pretty,
+        }
+    }
+    ///makes the directory if needed and returns the files that were written
+    pub fn write(&self, result: &PokedexSearchResult) -> io::Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(&self.dir)?;
+        let mut paths = Vec::new();
+        for (key, group) in result.split_by(self.split_by) {
+            let path = self.path_for(&key);
+            let mut writer = io::BufWriter::new(std::fs::File::create(&path)?);
+            group.write_data(
+                &mut writer,
+                self.write_type
+                    .writer(self.fields.clone(), self.pretty)
+                    .as_mut(),
+            )?;
+            writer.flush()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(file_stem(key))
+            .with_extension(self.write_type.extension())
+    }
+}
+
+///lowercase with anything that isn't safe in a file name turned into `-`
+fn file_stem(key: &str) -> String {
+    key.trim()
+        .chars()
+        .flat_map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c.to_lowercase().collect::<Vec<_>>()
+            } else {
+                vec!['-']
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,151 @@
+use std::io::{self, Write};
+
+use rusqlite::{Connection, MAIN_DB, params};
+
+use crate::{
+    Pokemon, data_types::EggGroup, data_types::PokemonType, string_id::kebab_case,
+    writing::PokemonWriter,
+};
+
+const SCHEMA: &str = "
+CREATE TABLE pokemon (
+    national_dex_number INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    genus TEXT NOT NULL,
+    color TEXT NOT NULL,
+    shape TEXT NOT NULL,
+    hp INTEGER NOT NULL,
+    attack INTEGER NOT NULL,
+    defence INTEGER NOT NULL,
+    special_attack INTEGER NOT NULL,
+    special_defence INTEGER NOT NULL,
+    speed INTEGER NOT NULL
+);
+CREATE TABLE pokemon_type (
+    national_dex_number INTEGER NOT NULL REFERENCES pokemon (national_dex_number),
+    slot INTEGER NOT NULL,
+    type TEXT NOT NULL,
+    PRIMARY KEY (national_dex_number, slot)
+);
+CREATE TABLE pokemon_ability (
+    national_dex_number INTEGER NOT NULL REFERENCES pokemon (national_dex_number),
+    slot INTEGER NOT NULL,
+    ability TEXT NOT NULL,
+    is_hidden INTEGER NOT NULL,
+    PRIMARY KEY (national_dex_number, slot)
+);
+CREATE TABLE pokemon_egg_group (
+    national_dex_number INTEGER NOT NULL REFERENCES pokemon (national_dex_number),
+    slot INTEGER NOT NULL,
+    egg_group TEXT NOT NULL,
+    PRIMARY KEY (national_dex_number, slot)
+);
+";
+
+///builds a sqlite database in memory and writes the whole file out at the end
+///
+///types, abilities and egg groups get their own tables that point back at `pokemon`
+pub(super) struct SqliteWriter {
+    connection: Option<Connection>,
+}
+impl SqliteWriter {
+    pub(super) fn new() -> Self {
+        Self { connection: None }
+    }
+    fn connection(&self) -> io::Result<&Connection> {
+        self.connection
+            .as_ref()
+            .ok_or_else(|| io::Error::other("sqlite writer was not started"))
+    }
+}
+
+impl PokemonWriter for SqliteWriter {
+    fn begin(&mut self, _writer: &mut dyn Write) -> io::Result<()> {
+        let connection = Connection::open_in_memory().map_err(io::Error::other)?;
+        connection.execute_batch(SCHEMA).map_err(io::Error::other)?;
+        self.connection = Some(connection);
+        Ok(())
+    }
+    fn record(&mut self, _writer: &mut dyn Write, pokemon: &Pokemon) -> io::Result<()> {
+        let connection = self.connection()?;
+        let dex_num = pokemon.get_dex_number().number();
+        let stats = pokemon.get_stats();
+        //the values are written like yaml and toml write them, `solar-power` and not `SolarPower`
+        let record = kebab_case(|| serde_json::to_value(pokemon))?;
+        let text = |key: &str| record[key].as_str().unwrap_or_default().to_string();
+        let inserted = connection
+            .execute(
+                "INSERT OR IGNORE INTO pokemon VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    dex_num,
+                    text("name"),
+                    text("genus"),
+                    text("color"),
+                    text("shape"),
+                    stats[0],
+                    stats[1],
+                    stats[2],
+                    stats[3],
+                    stats[4],
+                    stats[5],
+                ],
+            )
+            .map_err(io::Error::other)?;
+        //`or` searches can have the same pokemon more than once
+        if inserted == 0 {
+            return Ok(());
+        }
+
+        let types = [
+            ("type1", pokemon.get_primary_type()),
+            ("type2", pokemon.get_seconary_type()),
+        ];
+        for (slot, (key, ptype)) in (1_u8..).zip(types) {
+            if *ptype != PokemonType::None {
+                connection
+                    .execute(
+                        "INSERT INTO pokemon_type VALUES (?1, ?2, ?3)",
+                        params![dex_num, slot, text(key)],
+                    )
+                    .map_err(io::Error::other)?;
+            }
+        }
+        let abilities = [
+            ("ability1", pokemon.get_ability_1(), false),
+            ("ability2", pokemon.get_ability_2(), false),
+            ("hidden_ability", pokemon.get_hidden_ability(), true),
+        ];
+        for (slot, (key, ability, is_hidden)) in (1_u8..).zip(abilities) {
+            if ability.to_string() != "None" {
+                connection
+                    .execute(
+                        "INSERT INTO pokemon_ability VALUES (?1, ?2, ?3, ?4)",
+                        params![dex_num, slot, text(key), is_hidden],
+                    )
+                    .map_err(io::Error::other)?;
+            }
+        }
+        let egg_groups = [
+            ("egg_group1", pokemon.get_egg_group_1()),
+            ("egg_group2", pokemon.get_egg_group_2()),
+        ];
+        for (slot, (key, group)) in (1_u8..).zip(egg_groups) {
+            if *group != EggGroup::None {
+                connection
+                    .execute(
+                        "INSERT INTO pokemon_egg_group VALUES (?1, ?2, ?3)",
+                        params![dex_num, slot, text(key)],
+                    )
+                    .map_err(io::Error::other)?;
+            }
+        }
+        Ok(())
+    }
+    fn end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        let data = self
+            .connection()?
+            .serialize(MAIN_DB)
+            .map_err(io::Error::other)?;
+        writer.write_all(&data)
+    }
+}